@@ -237,6 +237,7 @@ fn testnet_genesis(
 		im_online: ImOnlineConfig { keys: vec![] },
 		grandpa: GrandpaConfig { authorities: vec![] },
 		transaction_payment: Default::default(),
+		treasury: Default::default(),
 		beefy: Default::default(),
 		octopus_appchain: OctopusAppchainConfig {
 			anchor_contract: "plats_network.near".to_string(),
@@ -461,6 +462,7 @@ fn plats_testnet_genesis(
 		im_online: ImOnlineConfig { keys: vec![] },
 		grandpa: GrandpaConfig { authorities: vec![] },
 		transaction_payment: Default::default(),
+		treasury: Default::default(),
 		beefy: Default::default(),
 		octopus_appchain: OctopusAppchainConfig {
 			anchor_contract: "plats-network.registry.test_oct.testnet".to_string(),
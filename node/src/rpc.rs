@@ -7,7 +7,9 @@
 
 use std::sync::Arc;
 
-use appchain_plats_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use appchain_plats_runtime::{
+	opaque::Block, AccountId, AssetId, Balance, BlockNumber, ClassId, Hash, Index,
+};
 use sc_client_api::AuxStore;
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_babe_rpc::BabeRpcHandler;
@@ -99,6 +101,8 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api:
+		pallet_task_rpc::TaskRuntimeApi<Block, AccountId, Balance, BlockNumber, AssetId, ClassId>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
@@ -107,6 +111,7 @@ where
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
 	use pallet_mmr_rpc::{Mmr, MmrApi};
+	use pallet_task_rpc::{Task, TaskApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -129,6 +134,7 @@ where
 	// These RPCs should use an asynchronous caller instead.
 	io.extend_with(MmrApi::to_delegate(Mmr::new(client.clone())));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
+	io.extend_with(TaskApi::to_delegate(Task::new(client.clone())));
 	io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(BabeRpcHandler::new(
 		client.clone(),
 		shared_epoch_changes.clone(),
@@ -0,0 +1,33 @@
+//! Runtime API definition for the task pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_task::{Campaign, CampaignId, RewardAsset};
+
+sp_api::decl_runtime_apis! {
+	pub trait TaskRuntimeApi<AccountId, Balance, BlockNumber, AssetId, ClassId> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		AssetId: Codec,
+		ClassId: Codec,
+	{
+		/// Details of a campaign, with the status it is in at the current block
+		fn campaign(
+			campaign_id: CampaignId,
+		) -> Option<Campaign<AccountId, Balance, BlockNumber, AssetId, ClassId>>;
+		/// Id of the campaign created with `campaign_ref`, the reference in the client's system
+		fn campaign_id(campaign_ref: Vec<u8>) -> Option<CampaignId>;
+		/// Budget of a campaign that can still be rewarded
+		fn remaining_budget(campaign_id: CampaignId) -> Option<Balance>;
+		/// Unclaimed rewards of `who` in `asset` and the block from which all of them can be claimed
+		fn claimable(who: AccountId, asset: RewardAsset<AssetId>) -> (Balance, BlockNumber);
+		/// Vesting rewards of `who` in `asset` that can be claimed now and that are still locked
+		fn vesting_balance(who: AccountId, asset: RewardAsset<AssetId>) -> (Balance, Balance);
+		/// Native balance of the campaign account that is not needed to keep it alive
+		fn remain_balance() -> Balance;
+	}
+}
@@ -0,0 +1,215 @@
+//! RPC interface for the task pallet.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_task_rpc_runtime_api::TaskRuntimeApi;
+use pallet_task_rpc_runtime_api::{Campaign, CampaignId, RewardAsset};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Reward of an account and the block from which it can be claimed.
+#[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardInfo<BlockNumber> {
+	/// Amount waiting to be claimed.
+	pub amount: NumberOrHex,
+	/// Block from which the amount can be claimed.
+	pub unlock_at: BlockNumber,
+}
+
+/// Vesting rewards of an account.
+#[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingInfo {
+	/// Amount released that can be claimed now.
+	pub claimable: NumberOrHex,
+	/// Amount not released yet.
+	pub locked: NumberOrHex,
+}
+
+#[rpc]
+pub trait TaskApi<BlockHash, AccountId, Balance, BlockNumber, AssetId, ClassId> {
+	/// Details of a campaign, with the status it is in at the current block.
+	#[rpc(name = "task_campaign")]
+	fn campaign(
+		&self,
+		campaign_id: CampaignId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Campaign<AccountId, Balance, BlockNumber, AssetId, ClassId>>>;
+
+	/// Id of the campaign created with `campaign_ref`, the reference in the client's system.
+	#[rpc(name = "task_campaignId")]
+	fn campaign_id(
+		&self,
+		campaign_ref: String,
+		at: Option<BlockHash>,
+	) -> Result<Option<CampaignId>>;
+
+	/// Budget of a campaign that can still be rewarded.
+	#[rpc(name = "task_remainingBudget")]
+	fn remaining_budget(
+		&self,
+		campaign_id: CampaignId,
+		at: Option<BlockHash>,
+	) -> Result<Option<NumberOrHex>>;
+
+	/// Unclaimed rewards of `who` in `asset` and the block from which all of them can be claimed.
+	#[rpc(name = "task_claimable")]
+	fn claimable(
+		&self,
+		who: AccountId,
+		asset: RewardAsset<AssetId>,
+		at: Option<BlockHash>,
+	) -> Result<RewardInfo<BlockNumber>>;
+
+	/// Vesting rewards of `who` in `asset` that can be claimed now and that are still locked.
+	#[rpc(name = "task_vestingBalance")]
+	fn vesting_balance(
+		&self,
+		who: AccountId,
+		asset: RewardAsset<AssetId>,
+		at: Option<BlockHash>,
+	) -> Result<VestingInfo>;
+
+	/// Native balance of the campaign account that is not needed to keep it alive.
+	#[rpc(name = "task_remainBalance")]
+	fn remain_balance(&self, at: Option<BlockHash>) -> Result<NumberOrHex>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The balance does not fit in the RPC representation.
+	BalanceOverflow,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::BalanceOverflow => 2,
+		}
+	}
+}
+
+/// An implementation of task specific RPC methods.
+pub struct Task<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Task<C, B> {
+	/// Create new `Task` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query the task pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn to_number_or_hex<Balance: TryInto<NumberOrHex>>(balance: Balance) -> Result<NumberOrHex> {
+	balance.try_into().map_err(|_| RpcError {
+		code: ErrorCode::ServerError(Error::BalanceOverflow.into()),
+		message: "Balance doesn't fit in NumberOrHex representation".into(),
+		data: None,
+	})
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, AssetId, ClassId>
+	TaskApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber, AssetId, ClassId>
+	for Task<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TaskRuntimeApi<Block, AccountId, Balance, BlockNumber, AssetId, ClassId>,
+	AccountId: Codec,
+	Balance: Codec + TryInto<NumberOrHex>,
+	BlockNumber: Codec,
+	AssetId: Codec,
+	ClassId: Codec,
+{
+	fn campaign(
+		&self,
+		campaign_id: CampaignId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Campaign<AccountId, Balance, BlockNumber, AssetId, ClassId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.campaign(&at, campaign_id).map_err(runtime_error)
+	}
+
+	fn campaign_id(
+		&self,
+		campaign_ref: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<CampaignId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.campaign_id(&at, campaign_ref.into_bytes()).map_err(runtime_error)
+	}
+
+	fn remaining_budget(
+		&self,
+		campaign_id: CampaignId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.remaining_budget(&at, campaign_id)
+			.map_err(runtime_error)?
+			.map(to_number_or_hex)
+			.transpose()
+	}
+
+	fn claimable(
+		&self,
+		who: AccountId,
+		asset: RewardAsset<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RewardInfo<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let (amount, unlock_at) = api.claimable(&at, who, asset).map_err(runtime_error)?;
+		Ok(RewardInfo { amount: to_number_or_hex(amount)?, unlock_at })
+	}
+
+	fn vesting_balance(
+		&self,
+		who: AccountId,
+		asset: RewardAsset<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<VestingInfo> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let (claimable, locked) = api.vesting_balance(&at, who, asset).map_err(runtime_error)?;
+		Ok(VestingInfo {
+			claimable: to_number_or_hex(claimable)?,
+			locked: to_number_or_hex(locked)?,
+		})
+	}
+
+	fn remain_balance(&self, at: Option<<Block as BlockT>::Hash>) -> Result<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		to_number_or_hex(api.remain_balance(&at).map_err(runtime_error)?)
+	}
+}
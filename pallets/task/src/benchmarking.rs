@@ -1,20 +1,469 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-task
 
 use super::*;
 
-#[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as Task;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+fn budget<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(1_000_000u32.into())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn create_campaign_of<T: Config>(
+	client: &T::AccountId,
+	start_block: T::BlockNumber,
+	badge_supply: Option<u32>,
+) -> CampaignId {
+	let campaign_id = Task::<T>::next_campaign_id();
+	Task::<T>::create_campaign(
+		RawOrigin::Signed(client.clone()).into(),
+		campaign_id.encode(),
+		budget::<T>(),
+		start_block,
+		100u32.into(),
+		RewardAsset::Native,
+		badge_supply,
+	)
+	.expect("client is funded; qed");
+	campaign_id
+}
+
+fn add_task_of<T: Config>(
+	client: &T::AccountId,
+	campaign_id: CampaignId,
+) -> Result<(), sp_runtime::DispatchError> {
+	Task::<T>::add_task(
+		RawOrigin::Signed(client.clone()).into(),
+		campaign_id,
+		TaskKind::Quiz,
+		T::Currency::minimum_balance(),
+		10,
+		100u32.into(),
+	)
+}
+
+fn submit_proofs_of<T: Config>(
+	campaign_id: CampaignId,
+	users: &[T::AccountId],
+) -> Result<(), sp_runtime::DispatchError> {
+	for user in users.iter() {
+		Task::<T>::submit_proof(
+			RawOrigin::Signed(user.clone()).into(),
+			campaign_id,
+			0,
+			T::Hash::default(),
+		)?;
+	}
+	Ok(())
+}
+
+fn reward_entry<T: Config>(amount: BalanceOf<T>) -> RewardEntryOf<T> {
+	RewardEntry {
+		asset: RewardAsset::Native,
+		amount,
+		claimed: Zero::zero(),
+		credited_at: Zero::zero(),
+	}
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	create_campaign {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let campaign_ref = vec![0u8; T::MaxCampaignRefLength::get() as usize];
+	}: _(
+		RawOrigin::Signed(caller),
+		campaign_ref,
+		budget::<T>(),
+		Zero::zero(),
+		100u32.into(),
+		RewardAsset::Native,
+		Some(1)
+	)
+	verify {
+		assert!(Campaigns::<T>::contains_key(0));
+	}
+
+	create_treasury_campaign {
+		let origin = T::TreasuryOrigin::successful_origin();
+		T::Currency::make_free_balance_be(
+			&T::TreasuryAccount::get(),
+			BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		let campaign_ref = vec![0u8; T::MaxCampaignRefLength::get() as usize];
+	}: _<T::Origin>(
+		origin,
+		campaign_ref,
+		budget::<T>(),
+		Zero::zero(),
+		100u32.into(),
+		RewardAsset::Native,
+		Some(1)
+	)
+	verify {
+		assert_eq!(Campaigns::<T>::get(0).unwrap().client, T::TreasuryAccount::get());
+	}
+
+	start_campaign {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, 10u32.into(), None);
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+	}: _(RawOrigin::Signed(client), campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Active);
+	}
+
+	pause_campaign {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+	}: _(RawOrigin::Signed(client), campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Paused);
+	}
+
+	resume_campaign {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		Task::<T>::pause_campaign(RawOrigin::Signed(client.clone()).into(), campaign_id)?;
+	}: _(RawOrigin::Signed(client), campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Active);
+	}
+
+	end_campaign {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+	}: _(RawOrigin::Signed(client), campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Ended);
+	}
+
+	settle_campaign {
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		Task::<T>::end_campaign(RawOrigin::Signed(client).into(), campaign_id)?;
+	}: _<T::Origin>(origin, campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Settled);
+	}
+
+	cancel_campaign {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+	}: _(RawOrigin::Signed(client), campaign_id)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign_id).unwrap().status, CampaignStatus::Cancelled);
+	}
+
+	// Worst case: the bond goes to another account
+	slash_bond {
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let bond = Campaigns::<T>::get(campaign_id).unwrap().bond;
+		let beneficiary: T::AccountId = account("user", 0, SEED);
+	}: _<T::Origin>(origin, campaign_id, bond, Some(beneficiary.clone()))
+	verify {
+		assert!(Campaigns::<T>::get(campaign_id).unwrap().bond.is_zero());
+	}
+
+	add_task {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let reward = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(client), campaign_id, TaskKind::Quiz, reward, 10, 100u32.into())
+	verify {
+		assert!(Tasks::<T>::contains_key(campaign_id, 0));
+	}
+
+	// Worst case: users complete a task they have a pending submission for, and every user also
+	// gets a badge minted
+	payment {
+		let u in 1 .. T::MaxUsersPerPayment::get().min(T::MaxPendingPayouts::get());
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), Some(u));
+		let users: Vec<T::AccountId> = (0..u).map(|i| account("user", i, SEED)).collect();
+		let amount = T::Currency::minimum_balance();
+		Task::<T>::add_task(
+			RawOrigin::Signed(client).into(),
+			campaign_id,
+			TaskKind::SocialFollow,
+			amount,
+			u,
+			100u32.into(),
+		)?;
+		submit_proofs_of::<T>(campaign_id, &users)?;
+	}: _<T::Origin>(origin, campaign_id, 0, Some(0), users, amount)
+	verify {
+		assert_eq!(PayoutQueueTail::<T>::get(), u);
+	}
+
+	// Worst case: each user already vests as many rewards as possible but one, has a pending
+	// submission and gets a badge
+	payment_vested {
+		let u in 1 .. T::MaxUsersPerPayment::get();
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), Some(u));
+		let schedule = VestingSchedule { cliff: 10u32.into(), duration: 100u32.into() };
+		Task::<T>::set_vesting(
+			RawOrigin::Signed(client.clone()).into(),
+			campaign_id,
+			Some(schedule),
+		)?;
+		let amount = T::Currency::minimum_balance();
+		let position =
+			VestingPosition { total: amount, claimed: Zero::zero(), start: Zero::zero(), schedule };
+		let users: Vec<T::AccountId> = (0..u).map(|i| account("user", i, SEED)).collect();
+		for user in users.iter() {
+			let positions: BoundedVec<_, _> =
+				vec![position; T::MaxVestingPositions::get().saturating_sub(1) as usize]
+					.try_into()
+					.expect("below MaxVestingPositions; qed");
+			VestingPositions::<T>::insert(user, RewardAsset::Native, positions);
+		}
+		Task::<T>::add_task(
+			RawOrigin::Signed(client).into(),
+			campaign_id,
+			TaskKind::SocialFollow,
+			amount,
+			u,
+			100u32.into(),
+		)?;
+		submit_proofs_of::<T>(campaign_id, &users)?;
+	}: payment<T::Origin>(origin, campaign_id, 0, Some(0), users, amount)
+	verify {
+		let spent = Task::<T>::campaigns(campaign_id).map(|campaign| campaign.spent);
+		assert_eq!(spent, Some(amount.saturating_mul(u.into())));
+		assert_eq!(PayoutQueueTail::<T>::get(), 0);
+	}
+
+	submit_proof {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		add_task_of::<T>(&client, campaign_id)?;
+		let user: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(user.clone()), campaign_id, 0, T::Hash::default())
+	verify {
+		assert!(Submissions::<T>::contains_key((campaign_id, 0), &user));
+	}
+
+	// Worst case: the user also gets a badge minted
+	approve_submission {
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), Some(1));
+		add_task_of::<T>(&client, campaign_id)?;
+		let user: T::AccountId = account("user", 0, SEED);
+		Task::<T>::submit_proof(
+			RawOrigin::Signed(user.clone()).into(),
+			campaign_id,
+			0,
+			T::Hash::default(),
+		)?;
+	}: _<T::Origin>(origin, campaign_id, 0, user.clone())
+	verify {
+		assert_eq!(
+			Submissions::<T>::get((campaign_id, 0), &user).unwrap().status,
+			SubmissionStatus::Approved
+		);
+	}
+
+	reject_submission {
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		add_task_of::<T>(&client, campaign_id)?;
+		let user: T::AccountId = account("user", 0, SEED);
+		Task::<T>::submit_proof(
+			RawOrigin::Signed(user.clone()).into(),
+			campaign_id,
+			0,
+			T::Hash::default(),
+		)?;
+	}: _<T::Origin>(origin, campaign_id, 0, user.clone())
+	verify {
+		assert_eq!(
+			Submissions::<T>::get((campaign_id, 0), &user).unwrap().status,
+			SubmissionStatus::Rejected
+		);
+	}
+
+	claim {
+		let origin = T::RewardOrigin::successful_origin();
+		let user: T::AccountId = account("user", 0, SEED);
+		let amount = T::Currency::minimum_balance().saturating_mul(10u32.into());
+		T::Currency::make_free_balance_be(&Task::<T>::account_id(), budget::<T>());
+		Rewards::<T>::insert(&user, 0, reward_entry::<T>(amount));
+		frame_system::Pallet::<T>::set_block_number(T::ClaimDuration::get());
+	}: _<T::Origin>(origin, 0, amount, user.clone())
+	verify {
+		assert!(!Rewards::<T>::contains_key(&user, 0));
+	}
+
+	claim_reward {
+		let user: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_mul(10u32.into());
+		T::Currency::make_free_balance_be(&Task::<T>::account_id(), budget::<T>());
+		Rewards::<T>::insert(&user, 0, reward_entry::<T>(amount));
+		frame_system::Pallet::<T>::set_block_number(T::ClaimDuration::get());
+	}: _(RawOrigin::Signed(user.clone()), 0, amount)
+	verify {
+		assert!(!Rewards::<T>::contains_key(&user, 0));
+	}
+
+	claim_all {
+		let c in 1 .. 100;
+		let user: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_mul(10u32.into());
+		T::Currency::make_free_balance_be(&Task::<T>::account_id(), budget::<T>());
+		for campaign_id in 0..c {
+			Rewards::<T>::insert(&user, campaign_id, reward_entry::<T>(amount));
+		}
+		frame_system::Pallet::<T>::set_block_number(T::ClaimDuration::get());
+	}: _(RawOrigin::Signed(user.clone()), c)
+	verify {
+		assert_eq!(Rewards::<T>::iter_prefix(&user).count(), 0);
+	}
+
+	publish_distribution {
+		let origin = T::RewardOrigin::successful_origin();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+	}: _<T::Origin>(origin, campaign_id, T::Hash::default(), budget::<T>(), u32::MAX)
+	verify {
+		assert!(Distributions::<T>::contains_key(campaign_id));
+	}
+
+	claim_distribution {
+		let p in 0 .. T::MaxProofLength::get();
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let user: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_mul(10u32.into());
+		let proof: Vec<T::Hash> = (0..p).map(|i| T::Hashing::hash_of(&i)).collect();
+		let root = Task::<T>::compute_root(Task::<T>::distribution_leaf(0, &user, amount), &proof);
+		Task::<T>::publish_distribution(
+			T::RewardOrigin::successful_origin(),
+			campaign_id,
+			root,
+			amount,
+			1,
+		)?;
+	}: _(RawOrigin::Signed(user.clone()), campaign_id, 0, amount, proof)
+	verify {
+		assert_eq!(ClaimedBitmap::<T>::get(campaign_id, 0), 1);
+	}
+
+	set_vesting {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let schedule = VestingSchedule { cliff: 10u32.into(), duration: 100u32.into() };
+	}: _(RawOrigin::Signed(client), campaign_id, Some(schedule))
+	verify {
+		assert_eq!(CampaignVesting::<T>::get(campaign_id), Some(schedule));
+	}
+
+	claim_vested {
+		let v in 1 .. T::MaxVestingPositions::get();
+		let user: T::AccountId = whitelisted_caller();
+		let amount = T::Currency::minimum_balance().saturating_mul(10u32.into());
+		T::Currency::make_free_balance_be(&Task::<T>::account_id(), budget::<T>());
+		let schedule = VestingSchedule { cliff: Zero::zero(), duration: 100u32.into() };
+		let position =
+			VestingPosition { total: amount, claimed: Zero::zero(), start: Zero::zero(), schedule };
+		let positions: BoundedVec<_, _> = vec![position; v as usize]
+			.try_into()
+			.expect("at most MaxVestingPositions; qed");
+		VestingPositions::<T>::insert(&user, RewardAsset::Native, positions);
+		frame_system::Pallet::<T>::set_block_number(100u32.into());
+	}: _(RawOrigin::Signed(user.clone()), RewardAsset::Native)
+	verify {
+		assert!(!VestingPositions::<T>::contains_key(&user, RewardAsset::Native));
+	}
+
+	grant_role {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(client), campaign_id, operator.clone(), CampaignRole::Operator)
+	verify {
+		assert_eq!(CampaignRoles::<T>::get(campaign_id, &operator), Some(CampaignRole::Operator));
+	}
+
+	revoke_role {
+		let client = funded_account::<T>("client", 0);
+		let campaign_id = create_campaign_of::<T>(&client, Zero::zero(), None);
+		let operator: T::AccountId = account("operator", 0, SEED);
+		CampaignRoles::<T>::insert(campaign_id, &operator, CampaignRole::Operator);
+	}: _(RawOrigin::Signed(client), campaign_id, operator.clone())
+	verify {
+		assert!(!CampaignRoles::<T>::contains_key(campaign_id, &operator));
+	}
+
+	add_admin {
+		let origin = T::AdminOrigin::successful_origin();
+		let user: T::AccountId = account("user", 0, SEED);
+	}: _<T::Origin>(origin, user.clone())
+	verify {
+		assert!(Admins::<T>::contains_key(&user));
+	}
+
+	remove_admin {
+		let origin = T::AdminOrigin::successful_origin();
+		let user: T::AccountId = account("user", 0, SEED);
+		Admins::<T>::insert(&user, true);
+		AdminCount::<T>::put(T::MinAdmins::get() + 1);
+	}: _<T::Origin>(origin, user.clone())
+	verify {
+		assert!(!Admins::<T>::contains_key(&user));
+	}
+
+	propose_admin_handover {
+		let caller: T::AccountId = whitelisted_caller();
+		Admins::<T>::insert(&caller, true);
+		let to: T::AccountId = account("user", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), to.clone())
+	verify {
+		assert_eq!(AdminHandovers::<T>::get(&caller), Some(to));
+	}
+
+	accept_admin_handover {
+		let caller: T::AccountId = whitelisted_caller();
+		let from: T::AccountId = account("admin", 0, SEED);
+		Admins::<T>::insert(&from, true);
+		AdminHandovers::<T>::insert(&from, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), from.clone())
+	verify {
+		assert!(Admins::<T>::contains_key(&caller));
+		assert!(!Admins::<T>::contains_key(&from));
+	}
+
+	// Worst case: the admin list was full before
+	force_set_admins {
+		let a in T::MinAdmins::get() .. T::MaxAdmins::get();
+		for i in 0..T::MaxAdmins::get() {
+			let admin: T::AccountId = account("admin", i, SEED);
+			Admins::<T>::insert(&admin, true);
+		}
+		let admins: Vec<T::AccountId> = (0..a).map(|i| account("user", i, SEED)).collect();
+	}: _(RawOrigin::Root, admins)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(AdminCount::<T>::get(), a);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(Task, crate::mock::new_test_ext(), crate::mock::Test);
 }
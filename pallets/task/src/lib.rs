@@ -8,39 +8,401 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-/*
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
-*/
+
+pub mod migrations;
+pub mod offchain;
+pub mod weights;
+pub use weights::WeightInfo;
 
 use codec::{Decode, Encode};
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement, ReservableCurrency, WithdrawReasons},
+	traits::{
+		tokens::{fungibles, nonfungibles},
+		BalanceStatus, Currency, ExistenceRequirement, Imbalance, OnRuntimeUpgrade, OnUnbalanced,
+		ReservableCurrency, StorageVersion, WithdrawReasons,
+	},
 	transactional, PalletId,
 };
-use frame_system::pallet_prelude::*;
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction},
+	pallet_prelude::*,
+};
 use scale_info::TypeInfo;
+use sp_core::crypto::KeyTypeId;
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedMul, SaturatedConversion, Saturating, Zero},
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedMul, Hash as HashT, One,
+		SaturatedConversion, Saturating, Zero,
+	},
 	ArithmeticError, Permill,
 };
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_set::BTreeSet, convert::TryInto, vec::Vec};
+
+/// Number of taken badge class ids skipped before creating a campaign with badges fails
+pub const MAX_BADGE_CLASS_ATTEMPTS: u32 = 16;
+
+/// Number of times an automatic payout is tried before the reward is left to be claimed
+pub const MAX_PAYOUT_ATTEMPTS: u32 = 3;
+
+/// Key type of the accounts sending the verdicts of the offchain worker
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"task");
+
+/// Crypto of the offchain worker keys.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::app_crypto::{app_crypto, sr25519};
+	app_crypto!(sr25519, KEY_TYPE);
+}
 
-pub type CampaignIndex = Vec<u8>;
+/// Identity of an offchain worker key.
+pub type AuthorityId = crypto::Public;
+
+/// Numeric id assigned by the pallet to every new campaign
+pub type CampaignId = u32;
+
+/// Index of a task within its campaign
+pub type TaskId = u32;
+
+/// Id given by the caller to a payment of a campaign, so that the payment is made only once
+pub type BatchId = u64;
+
+/// Reference of a campaign in the client's own system (e.g. the web app UUID)
+pub type CampaignRefOf<T> = BoundedVec<u8, <T as Config>::MaxCampaignRefLength>;
 
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+pub type CampaignOf<T> = Campaign<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::AssetId,
+	<T as Config>::ClassId,
+>;
+
+pub type RewardAssetOf<T> = RewardAsset<<T as Config>::AssetId>;
+
+pub type RewardEntryOf<T> =
+	RewardEntry<BalanceOf<T>, <T as frame_system::Config>::BlockNumber, <T as Config>::AssetId>;
+
+pub type TaskInfoOf<T> = TaskInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+pub type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber>;
+
+pub type VestingPositionOf<T> =
+	VestingPosition<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+pub type SubmissionOf<T> =
+	Submission<<T as frame_system::Config>::Hash, <T as frame_system::Config>::BlockNumber>;
+
+pub type DistributionOf<T> = Distribution<<T as frame_system::Config>::Hash, BalanceOf<T>>;
+
+/// Token a campaign is funded and rewarded with.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum RewardAsset<AssetId> {
+	/// The native currency of the chain.
+	Native,
+	/// A token of the assets pallet, e.g. the client's own token.
+	Asset(AssetId),
+}
+
+/// Lifecycle of a campaign.
+///
+/// `Draft -> Active <-> Paused -> Ended -> Settled`, and any campaign that has not ended yet can
+/// be `Cancelled`.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum CampaignStatus {
+	/// Created and funded, waiting for `start_block`.
+	Draft,
+	/// Running, users can be rewarded.
+	Active,
+	/// Temporarily stopped by the client or an admin.
+	Paused,
+	/// No longer running, remaining rewards can still be paid.
+	Ended,
+	/// All rewards have been paid, nothing can change anymore.
+	Settled,
+	/// Stopped before it ended.
+	Cancelled,
+}
+
+impl CampaignStatus {
+	/// Check if a campaign in this status can move to `next`.
+	pub fn can_transition_to(&self, next: CampaignStatus) -> bool {
+		use CampaignStatus::*;
+		matches!(
+			(self, next),
+			(Draft, Active) |
+				(Active, Paused) |
+				(Paused, Active) |
+				(Active, Ended) |
+				(Paused, Ended) |
+				(Ended, Settled) |
+				(Draft, Cancelled) |
+				(Active, Cancelled) |
+				(Paused, Cancelled)
+		)
+	}
+
+	/// Check if users can be rewarded from a campaign in this status.
+	pub fn is_payable(&self) -> bool {
+		matches!(self, CampaignStatus::Active | CampaignStatus::Ended)
+	}
+
+	/// Check if tasks can still be added to a campaign in this status.
+	pub fn is_editable(&self) -> bool {
+		matches!(self, CampaignStatus::Draft | CampaignStatus::Active | CampaignStatus::Paused)
+	}
+}
+
+/// What users have to do to complete a task.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum TaskKind {
+	/// Follow an account on a social network.
+	SocialFollow,
+	/// Answer a quiz.
+	Quiz,
+	/// Make a transaction on chain.
+	OnChainAction,
+}
+
+/// Task advertised by a campaign, each completion is rewarded with the same amount.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct TaskInfo<Balance, BlockNumber> {
+	/// What users have to do.
+	kind: TaskKind,
+	/// The amount paid to every user completing the task.
+	reward: Balance,
+	/// The maximum number of users that can be rewarded for the task.
+	max_completions: u32,
+	/// The number of users already rewarded for the task.
+	completions: u32,
+	/// The last block at which users can be rewarded for the task.
+	deadline: BlockNumber,
+}
+
+/// Role of an account in a single campaign.
+///
+/// The client of a campaign is always its owner, other accounts are appointed by an owner.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum CampaignRole {
+	/// Manages the campaign lifecycle and its roles, and rewards users.
+	Owner,
+	/// Rewards users of the campaign.
+	Operator,
+	/// Follows the campaign, can not change anything.
+	Auditor,
+}
+
+impl CampaignRole {
+	/// Check if this role grants everything `other` is allowed to do.
+	pub fn includes(&self, other: CampaignRole) -> bool {
+		use CampaignRole::*;
+		matches!(
+			(self, other),
+			(Owner, _) | (Operator, Operator) | (Operator, Auditor) | (Auditor, Auditor)
+		)
+	}
+}
+
+/// How the rewards of a campaign are released to users.
+///
+/// Nothing is released during `cliff` blocks after a reward, then it is released linearly over
+/// `duration` blocks.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct VestingSchedule<BlockNumber> {
+	/// Blocks during which nothing is released.
+	pub cliff: BlockNumber,
+	/// Blocks over which the reward is released after the cliff.
+	pub duration: BlockNumber,
+}
+
+/// Reward of a user released following the vesting schedule of its campaign.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct VestingPosition<Balance, BlockNumber> {
+	/// The whole reward.
+	total: Balance,
+	/// The amount of the reward already claimed.
+	claimed: Balance,
+	/// The block at which the user was rewarded.
+	start: BlockNumber,
+	/// The schedule of the campaign at that time.
+	schedule: VestingSchedule<BlockNumber>,
+}
+
+impl<Balance, BlockNumber> VestingPosition<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// The amount of the reward released at `now`, including what has been claimed.
+	pub fn vested(&self, now: BlockNumber) -> Balance {
+		let unlock = self.start.saturating_add(self.schedule.cliff);
+		if now < unlock {
+			return Zero::zero()
+		}
+		let elapsed = now - unlock;
+		if elapsed >= self.schedule.duration {
+			return self.total
+		}
+		multiply_by_rational(
+			self.total.saturated_into(),
+			elapsed.saturated_into(),
+			self.schedule.duration.saturated_into(),
+		)
+		.map(|vested| vested.saturated_into())
+		.unwrap_or(self.total)
+	}
+
+	/// The amount released at `now` that has not been claimed yet.
+	pub fn claimable(&self, now: BlockNumber) -> Balance {
+		self.vested(now).saturating_sub(self.claimed)
+	}
+}
+
+/// Review state of a proof of completion.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum SubmissionStatus {
+	/// Waiting for an operator of the campaign.
+	Pending,
+	/// The user has been rewarded for the task.
+	Approved,
+	/// The proof was not accepted, the user can submit another one.
+	Rejected,
+}
+
+/// Proof submitted by a user that a task has been completed.
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
-pub struct Campaign<AccountId, Balance> {
+pub struct Submission<Hash, BlockNumber> {
+	/// Hash of the proof kept off chain, e.g. a screenshot or the answers of a quiz.
+	proof: Hash,
+	/// Current review state.
+	status: SubmissionStatus,
+	/// The block at which the proof was submitted.
+	submitted_at: BlockNumber,
+}
+
+/// NFT badge minted to every user rewarded by a campaign.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Badge<ClassId> {
+	/// The class created for the campaign badges.
+	class: ClassId,
+	/// The maximum number of badges that can be minted.
+	max_supply: u32,
+	/// The number of badges already minted, also the id of the next one.
+	minted: u32,
+}
+
+/// Rewards of a campaign published as a Merkle root, claimed by the users with a proof.
+///
+/// Leaves are `hash(index, account, amount)` and pairs of nodes are hashed in sorted order.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Distribution<Hash, Balance> {
+	/// The Merkle root of all the leaves.
+	root: Hash,
+	/// The sum of the amounts of all the leaves, taken from the campaign budget.
+	total: Balance,
+	/// The amount already claimed by users.
+	claimed: Balance,
+	/// The number of leaves, valid indices are below it.
+	leaves: u32,
+}
+
+/// Rewards of a user from a single campaign.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct RewardEntry<Balance, BlockNumber, AssetId> {
+	/// The token the user is rewarded with.
+	asset: RewardAsset<AssetId>,
+	/// The total amount credited to the user by the campaign.
+	amount: Balance,
+	/// The amount of `amount` already claimed.
+	claimed: Balance,
+	/// The block at which the user was last credited by the campaign.
+	credited_at: BlockNumber,
+}
+
+impl<Balance: Saturating + Copy, BlockNumber, AssetId> RewardEntry<Balance, BlockNumber, AssetId> {
+	/// The amount credited that has not been claimed yet.
+	pub fn unclaimed(&self) -> Balance {
+		self.amount.saturating_sub(self.claimed)
+	}
+}
+
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Campaign<AccountId, Balance, BlockNumber, AssetId, ClassId> {
 	/// The account creating campaign it.
 	client: AccountId,
 	/// The (total) amount that should be paid if the campaign is accepted.
 	value: Balance,
 	/// The amount held on deposit (reserved) for making this campaign.
+	/// Held until the campaign is closed, less what has been slashed.
 	bond: Balance,
+	/// The amount of `value` already rewarded to users.
+	spent: Balance,
+	/// Lifecycle status of the campaign, as of the last call that changed it.
+	/// Transitions due at `start_block` and `end_block` are stored by the next payment or
+	/// lifecycle call, see `status_at`.
+	status: CampaignStatus,
+	/// The block from which the campaign can be started.
+	start_block: BlockNumber,
+	/// The block at which the campaign is planned to end.
+	end_block: BlockNumber,
+	/// The token `value` is funded in and users are rewarded with.
+	asset: RewardAsset<AssetId>,
+	/// The badge users are rewarded with besides tokens, if any.
+	badge: Option<Badge<ClassId>>,
+}
+
+impl<AccountId, Balance: Saturating + Copy, BlockNumber: PartialOrd, AssetId, ClassId>
+	Campaign<AccountId, Balance, BlockNumber, AssetId, ClassId>
+{
+	/// The status of the campaign at `now`, once the transitions due at its blocks are made:
+	/// a `Draft` campaign is `Active` from `start_block`, a running one is `Ended` after
+	/// `end_block`.
+	pub fn status_at(&self, now: BlockNumber) -> CampaignStatus {
+		use CampaignStatus::*;
+		let status = match self.status {
+			Draft if now >= self.start_block => Active,
+			status => status,
+		};
+		match status {
+			Active | Paused if now > self.end_block => Ended,
+			status => status,
+		}
+	}
+
+	/// The amount of `value` that can still be rewarded to users.
+	/// Nothing is left once the campaign is closed, the rest has been refunded to the client.
+	pub fn remaining(&self) -> Balance
+	where
+		Balance: Zero,
+	{
+		match self.status {
+			CampaignStatus::Settled | CampaignStatus::Cancelled => Zero::zero(),
+			_ => self.value.saturating_sub(self.spent),
+		}
+	}
 }
 
 #[frame_support::pallet]
@@ -49,7 +411,7 @@ pub mod pallet {
 	pub use super::*;
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
@@ -62,7 +424,29 @@ pub mod pallet {
 		#[pallet::constant]
 		type CampaignDeposit: Get<Permill>;
 
-		// type RewardOrigin: EnsureOrigin<Self::Origin>;
+		/// Handler for the bonds slashed without a beneficiary, e.g. the treasury
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Part of `value` paid by the client on top of it when funding a campaign
+		#[pallet::constant]
+		type PlatformFee: Get<Permill>;
+
+		/// Handler for the platform fees in the native currency, e.g. the treasury
+		/// Fees in an asset are transferred to `TreasuryAccount`, campaigns it funds pay no fee
+		type FeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Origin allowed to create campaigns funded by `TreasuryAccount`
+		type TreasuryOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Account of the treasury pot, client of the campaigns created by `TreasuryOrigin`
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Origin allowed to reward users and to manage any campaign, e.g. Root, a collective or
+		/// `EnsureAdmin`
+		type RewardOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to manage the `Admins` list
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
 
 		// Duration that user can claim their token reward
 		type ClaimDuration: Get<Self::BlockNumber>;
@@ -73,21 +457,212 @@ pub mod pallet {
 		/// The task's pallet id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// Maximum length of the external reference of a campaign
+		#[pallet::constant]
+		type MaxCampaignRefLength: Get<u32>;
+
+		/// Maximum number of rewards of a campaign waiting for automatic payout
+		#[pallet::constant]
+		type MaxPendingPayouts: Get<u32>;
+
+		/// Maximum number of automatic payouts made in a block
+		#[pallet::constant]
+		type MaxPayoutsPerBlock: Get<u32>;
+
+		/// Maximum number of users rewarded by a single `payment`
+		#[pallet::constant]
+		type MaxUsersPerPayment: Get<u32>;
+
+		/// Maximum length of a distribution proof, i.e. depth of the Merkle tree
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// Maximum number of tasks of a campaign
+		#[pallet::constant]
+		type MaxTasks: Get<u32>;
+
+		/// Number of admins that can not be removed, so that `add_admin` stays usable
+		#[pallet::constant]
+		type MinAdmins: Get<u32>;
+
+		/// Maximum number of admins
+		#[pallet::constant]
+		type MaxAdmins: Get<u32>;
+
+		/// Identifier of the assets a campaign can be funded with
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// Assets a campaign can be funded with besides the native currency
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = BalanceOf<Self>>
+			+ fungibles::Transfer<Self::AccountId>;
+
+		/// Identifier of the NFT classes of campaign badges
+		type ClassId: Member + Parameter + Copy + MaxEncodedLen + AtLeast32BitUnsigned;
+
+		/// Identifier of campaign badges within their class
+		type InstanceId: Member + Parameter + Copy + From<u32>;
+
+		/// NFTs used to reward users with badges
+		type Uniques: nonfungibles::Create<
+				Self::AccountId,
+				ClassId = Self::ClassId,
+				InstanceId = Self::InstanceId,
+			> + nonfungibles::Mutate<Self::AccountId>;
+
+		/// Badge classes are taken from `BadgeClassStart` upwards, ids already created in
+		/// `Uniques` are skipped
+		/// Class ids below are left for other pallets
+		#[pallet::constant]
+		type BadgeClassStart: Get<Self::ClassId>;
+
+		/// Deposit paid by the client for the badge class of a campaign, the class deposit of
+		/// `Uniques`
+		#[pallet::constant]
+		type BadgeClassDeposit: Get<BalanceOf<Self>>;
+
+		/// Deposit paid by the client for each badge minted, the instance deposit of `Uniques`
+		#[pallet::constant]
+		type BadgeDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of rewards of a user in an asset that are vesting at the same time, the
+		/// next ones are added to the latest
+		#[pallet::constant]
+		type MaxVestingPositions: Get<u32>;
+
+		/// Key signing the verdicts of the offchain worker
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Maximum number of submissions verified by the offchain worker in a block
+		#[pallet::constant]
+		type MaxVerificationsPerBlock: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Campaign that have been made.
 	#[pallet::storage]
 	#[pallet::getter(fn campaigns)]
-	pub type Campaigns<T: Config> = StorageMap<
+	pub type Campaigns<T: Config> =
+		StorageMap<_, Twox64Concat, CampaignId, CampaignOf<T>, OptionQuery>;
+
+	/// Id that will be assigned to the next campaign
+	#[pallet::storage]
+	#[pallet::getter(fn next_campaign_id)]
+	pub type NextCampaignId<T: Config> = StorageValue<_, CampaignId, ValueQuery>;
+
+	/// Badge class id tried first for the next campaign with badges
+	/// Unset until the first one, which tries `BadgeClassStart + campaign id`
+	#[pallet::storage]
+	pub type NextBadgeClass<T: Config> = StorageValue<_, T::ClassId, OptionQuery>;
+
+	/// Campaign id of each external reference, a reference can only be used once
+	#[pallet::storage]
+	#[pallet::getter(fn campaign_of_ref)]
+	pub type CampaignRefs<T: Config> =
+		StorageMap<_, Blake2_128Concat, CampaignRefOf<T>, CampaignId, OptionQuery>;
+
+	/// Tasks advertised by each campaign
+	#[pallet::storage]
+	#[pallet::getter(fn tasks)]
+	pub type Tasks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CampaignId,
+		Twox64Concat,
+		TaskId,
+		TaskInfoOf<T>,
+		OptionQuery,
+	>;
+
+	/// Id that will be assigned to the next task of each campaign, also its number of tasks
+	#[pallet::storage]
+	#[pallet::getter(fn next_task_id)]
+	pub type NextTaskId<T: Config> = StorageMap<_, Twox64Concat, CampaignId, TaskId, ValueQuery>;
+
+	/// Vesting schedule of the campaigns releasing their rewards over time
+	#[pallet::storage]
+	#[pallet::getter(fn campaign_vesting)]
+	pub type CampaignVesting<T: Config> =
+		StorageMap<_, Twox64Concat, CampaignId, VestingScheduleOf<T>, OptionQuery>;
+
+	/// Rewards of each user in each asset that are being released over time
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_positions)]
+	pub type VestingPositions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		RewardAssetOf<T>,
+		BoundedVec<VestingPositionOf<T>, T::MaxVestingPositions>,
+		ValueQuery,
+	>;
+
+	/// Users already rewarded for each task of a campaign, so that a task is not paid twice
+	/// Campaigns without tasks reward users in as many payments as they want, retries are
+	/// recognized by their batch
+	#[pallet::storage]
+	#[pallet::getter(fn rewarded)]
+	pub type Rewarded<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CampaignId, TaskId),
+		Twox64Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Payment batches already made in each campaign, a batch paid again rewards nobody
+	#[pallet::storage]
+	pub type PaidBatches<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CampaignId, Twox64Concat, BatchId, bool, ValueQuery>;
+
+	/// Proofs of completion submitted by users for each task of a campaign
+	#[pallet::storage]
+	#[pallet::getter(fn submissions)]
+	pub type Submissions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CampaignId, TaskId),
+		Twox64Concat,
+		T::AccountId,
+		SubmissionOf<T>,
+		OptionQuery,
+	>;
+
+	/// Index of the `Pending` entries of `Submissions`, verified by the offchain worker
+	#[pallet::storage]
+	pub type PendingSubmissions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CampaignId, TaskId),
+		Twox64Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Roles appointed in a campaign, the client is not listed as it is always the owner
+	#[pallet::storage]
+	#[pallet::getter(fn campaign_roles)]
+	pub type CampaignRoles<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
-		CampaignIndex,
-		Campaign<T::AccountId, BalanceOf<T>>,
+		CampaignId,
+		Twox64Concat,
+		T::AccountId,
+		CampaignRole,
 		OptionQuery,
 	>;
 
@@ -95,30 +670,214 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn admins)]
 	pub type Admins<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, bool, OptionQuery>;
-	/// Store balance of user that system pay when user finish campaign
+
+	/// Number of accounts in `Admins`
+	#[pallet::storage]
+	#[pallet::getter(fn admin_count)]
+	pub type AdminCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Admin seat handovers waiting to be accepted: current admin to the proposed account
+	#[pallet::storage]
+	#[pallet::getter(fn admin_handovers)]
+	pub type AdminHandovers<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Rewards of each user from each campaign, removed once fully claimed
+	#[pallet::storage]
+	#[pallet::getter(fn rewards)]
+	pub type Rewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CampaignId,
+		RewardEntryOf<T>,
+		OptionQuery,
+	>;
+
+	/// Rewards waiting for automatic payout: user, campaign, the block from which it is due and
+	/// the number of payouts that already failed
+	/// Entries are pushed at `PayoutQueueTail` and paid from `PayoutQueueHead`
+	#[pallet::storage]
+	pub type PayoutQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(T::AccountId, CampaignId, T::BlockNumber, u32),
+		OptionQuery,
+	>;
+
+	/// Index of the next entry of `PayoutQueue` to pay
+	#[pallet::storage]
+	pub type PayoutQueueHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Index where the next entry of `PayoutQueue` is pushed
+	#[pallet::storage]
+	pub type PayoutQueueTail<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of entries of `PayoutQueue` for each campaign, at most `MaxPendingPayouts`
+	#[pallet::storage]
+	pub type PendingPayouts<T: Config> = StorageMap<_, Twox64Concat, CampaignId, u32, ValueQuery>;
+
+	/// Platform fees in each asset held by the campaign account until `TreasuryAccount` can
+	/// receive them, as a transfer below the minimum balance can not create its account
+	#[pallet::storage]
+	pub type PendingFees<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, BalanceOf<T>, ValueQuery>;
+
+	/// Whether user balances of storage version 2 are still being moved to `Rewards`, see
+	/// `migrations::v3`
+	#[pallet::storage]
+	pub type LegacyBalancesLeft<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Merkle distribution published for a campaign
+	#[pallet::storage]
+	#[pallet::getter(fn distributions)]
+	pub type Distributions<T: Config> =
+		StorageMap<_, Twox64Concat, CampaignId, DistributionOf<T>, OptionQuery>;
+
+	/// Claimed leaves of a distribution, 32 indices per word
 	#[pallet::storage]
-	#[pallet::getter(fn balance_of)]
-	pub type BalanceUser<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+	pub type ClaimedBitmap<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CampaignId, Twox64Concat, u32, u32, ValueQuery>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// New campaign.
 		NewCampaign {
-			campaign_index: CampaignIndex,
+			campaign_id: CampaignId,
+			campaign_ref: CampaignRefOf<T>,
+			status: CampaignStatus,
+		},
+		/// Campaign moved to another lifecycle status.
+		CampaignStatusChanged {
+			campaign_id: CampaignId,
+			from: CampaignStatus,
+			to: CampaignStatus,
+		},
+		/// Campaign is closed, unused budget is refunded and bond released to the client.
+		CampaignClosed {
+			campaign_id: CampaignId,
+			refunded: BalanceOf<T>,
+			bond: BalanceOf<T>,
+		},
+		/// What is left of the bond is unreserved for the client.
+		BondReleased {
+			campaign_id: CampaignId,
+			client: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Part of the bond is taken from the client, given to `beneficiary` or to `Slash`.
+		BondSlashed {
+			campaign_id: CampaignId,
+			client: T::AccountId,
+			amount: BalanceOf<T>,
+			beneficiary: Option<T::AccountId>,
 		},
 		DepositClient {
-			campaign_index: CampaignIndex,
+			campaign_id: CampaignId,
+			asset: RewardAssetOf<T>,
 			deposit_amount: BalanceOf<T>,
+			/// Platform fee paid by the client on top of `deposit_amount`.
+			fee: BalanceOf<T>,
 		},
 
+		/// A task has been added to a campaign.
+		TaskAdded {
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			kind: TaskKind,
+			reward: BalanceOf<T>,
+			max_completions: u32,
+			deadline: T::BlockNumber,
+		},
+		/// A user submitted a proof of completion of a task.
+		ProofSubmitted {
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			user: T::AccountId,
+			proof: T::Hash,
+		},
+		/// A submission has been approved and the user rewarded with the task reward.
+		SubmissionApproved {
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			user: T::AccountId,
+			proof: T::Hash,
+			amount: BalanceOf<T>,
+		},
+		/// A submission has been rejected.
+		SubmissionRejected {
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			user: T::AccountId,
+			proof: T::Hash,
+		},
 		Payment {
-			campaign_index: CampaignIndex,
-			account: Vec<T::AccountId>,
+			campaign_id: CampaignId,
+			task_id: Option<TaskId>,
+			account: BoundedVec<T::AccountId, T::MaxUsersPerPayment>,
+		},
+		/// Users of a payment already rewarded for the task, or repeated in it, were not rewarded
+		/// again.
+		DuplicatesSkipped {
+			campaign_id: CampaignId,
+			task_id: Option<TaskId>,
+			account: BoundedVec<T::AccountId, T::MaxUsersPerPayment>,
 		},
 		Claim {
 			user: T::AccountId,
+			campaign_id: CampaignId,
+			asset: RewardAssetOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Rewards of a campaign are released following a vesting schedule.
+		VestingSet {
+			campaign_id: CampaignId,
+			schedule: VestingScheduleOf<T>,
+		},
+		/// Rewards of a campaign are not vesting anymore.
+		VestingRemoved {
+			campaign_id: CampaignId,
+		},
+		/// A user claimed the released part of its vesting rewards.
+		VestedClaimed {
+			user: T::AccountId,
+			asset: RewardAssetOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Reward not withdrawn after `PayoutDuration` has been paid to the user.
+		AutoPaid {
+			user: T::AccountId,
+			campaign_id: CampaignId,
+			asset: RewardAssetOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Rewards of a campaign are published as a Merkle root.
+		DistributionPublished {
+			campaign_id: CampaignId,
+			root: T::Hash,
+			total: BalanceOf<T>,
+		},
+		/// A leaf of a distribution has been claimed by its user.
+		DistributionClaimed {
+			campaign_id: CampaignId,
+			index: u32,
+			user: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// An account has been appointed to a role of a campaign.
+		RoleGranted {
+			campaign_id: CampaignId,
+			who: T::AccountId,
+			role: CampaignRole,
+		},
+		/// An account no longer has a role in a campaign.
+		RoleRevoked {
+			campaign_id: CampaignId,
+			who: T::AccountId,
+			role: CampaignRole,
 		},
 		AddAdmin {
 			user: T::AccountId,
@@ -126,6 +885,31 @@ pub mod pallet {
 		RemoveAdmin {
 			user: T::AccountId,
 		},
+		/// An admin proposed to hand over its seat to another account.
+		AdminHandoverProposed {
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+		/// The proposed account accepted the seat, `from` is no longer an admin.
+		AdminHandedOver {
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+		/// The whole admin list has been replaced.
+		AdminsForceSet {
+			count: u32,
+		},
+		/// Automatic payout of a reward failed `MAX_PAYOUT_ATTEMPTS` times, it is left to be
+		/// claimed by the user.
+		AutoPayoutAbandoned {
+			user: T::AccountId,
+			campaign_id: CampaignId,
+		},
+		/// The batch of a payment was already paid, nobody was rewarded again.
+		BatchAlreadyPaid {
+			campaign_id: CampaignId,
+			batch_id: BatchId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -137,6 +921,49 @@ pub mod pallet {
 		UserNotReward,
 		CanNotClaim,
 		PermissionDeny,
+		InvalidCampaignPeriod,
+		InvalidStatusTransition,
+		CampaignNotStarted,
+		CampaignNotPayable,
+		CampaignAlreadyPaid,
+		CampaignAlreadyExist,
+		CampaignRefTooLong,
+		PayoutQueueFull,
+		InvalidBadgeSupply,
+		BadgeSupplyExceeded,
+		DistributionAlreadyExist,
+		DistributionNotExist,
+		InvalidProof,
+		AlreadyClaimed,
+		TooManyUsers,
+		RoleNotExist,
+		ClientRoleFixed,
+		AlreadyAdmin,
+		NotAdmin,
+		TooFewAdmins,
+		TooManyAdmins,
+		CannotRemoveSelf,
+		HandoverNotExist,
+		InsufficientBond,
+		CampaignNotEditable,
+		InvalidTask,
+		TooManyTasks,
+		TaskNotExist,
+		TaskRequired,
+		TaskExpired,
+		TaskCompletionsExceeded,
+		RewardMismatch,
+		SubmissionAlreadyExist,
+		SubmissionNotExist,
+		SubmissionNotPending,
+		VestingAlreadyStarted,
+		TooManyVestingPositions,
+		NothingVested,
+		NothingToClaim,
+		AlreadyRewarded,
+		CampaignExpired,
+		BadgeClassUnavailable,
+		DistributionWithVesting,
 	}
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -166,6 +993,48 @@ pub mod pallet {
 			for admin in self.admins.iter() {
 				Admins::<T>::insert(admin, true);
 			}
+			let count = Admins::<T>::iter().count() as u32;
+			assert!(count <= T::MaxAdmins::get(), "too many genesis admins");
+			AdminCount::<T>::put(count);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Pay rewards that users have not withdrawn after `PayoutDuration`, then move user
+		/// balances left by the v3 migration
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let used_weight = Self::process_payout_queue(now, remaining_weight);
+			used_weight.saturating_add(migrations::v3::migrate_balances::<T>(
+				remaining_weight.saturating_sub(used_weight),
+			))
+		}
+
+		/// Verify pending submissions against the configured endpoint, see `offchain`
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::verify_submissions(now) {
+				log::debug!(target: "runtime::task", "submissions not verified: {}", e);
+			}
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::MigrateToV1::<T>::on_runtime_upgrade()
+				.saturating_add(migrations::v2::MigrateToV2::<T>::on_runtime_upgrade())
+				.saturating_add(migrations::v3::MigrateToV3::<T>::on_runtime_upgrade())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::v1::MigrateToV1::<T>::pre_upgrade()?;
+			migrations::v2::MigrateToV2::<T>::pre_upgrade()?;
+			migrations::v3::MigrateToV3::<T>::pre_upgrade()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::v1::MigrateToV1::<T>::post_upgrade()?;
+			migrations::v2::MigrateToV2::<T>::post_upgrade()?;
+			migrations::v3::MigrateToV3::<T>::post_upgrade()
 		}
 	}
 
@@ -174,112 +1043,719 @@ pub mod pallet {
 		///Create a campaign
 		/// Should be reserved token first
 		/// Store on chain
-		#[pallet::weight(10_000)]
+		/// The campaign gets the next campaign id, `campaign_ref` is the reference of the
+		/// campaign in the client's system and can not be reused
+		/// The campaign is `Active` right away if `start_block` has been reached, `Draft`
+		/// otherwise
+		/// `value` is paid in `asset`, the bond is always reserved in the native currency
+		/// The client also pays `PlatformFee` of `value` in `asset`, to `FeeDestination` in the
+		/// native currency or to `TreasuryAccount` in an asset, it is not refunded with the unused
+		/// budget
+		/// The first campaign funded in an asset also pays the minimum balance of the asset, kept
+		/// by the campaign account
+		/// With `badge_supply`, rewarded users also get a badge of a class created for the
+		/// campaign, at most `badge_supply` of them. The pallet owns the class so badges can only
+		/// be minted by rewarding users, the client pays its deposits
+		#[pallet::weight(T::WeightInfo::create_campaign())]
+		#[transactional]
 		pub fn create_campaign(
 			origin: OriginFor<T>,
-			campaign_index: CampaignIndex,
+			campaign_ref: Vec<u8>,
 			#[pallet::compact] value: BalanceOf<T>,
+			start_block: T::BlockNumber,
+			end_block: T::BlockNumber,
+			asset: RewardAssetOf<T>,
+			badge_supply: Option<u32>,
 		) -> DispatchResult {
 			let client = ensure_signed(origin)?;
 
-			let bond = (T::CampaignDepositMinimum::get()).max(T::CampaignDeposit::get() * value);
-			// Reserved balance for client
-			let _ = T::Currency::reserve(&client, bond)?;
-			Campaigns::<T>::insert(
-				&campaign_index,
-				Campaign { client: client.clone(), value, bond },
-			);
-
-			Self::deposit_campaign_account(&client, campaign_index.clone())?;
-
-			Self::deposit_event(Event::NewCampaign { campaign_index });
-
-			Ok(())
+			Self::do_create_campaign(
+				client,
+				campaign_ref,
+				value,
+				start_block,
+				end_block,
+				asset,
+				badge_supply,
+			)
 		}
 
-		/// Reward for all users with specific campaigns
+		/// Create a campaign funded from the treasury pot, `TreasuryAccount` is its client
+		/// Same rules as `create_campaign`: the treasury reserves the bond, pays `value` and
+		/// the platform fee, and gets back the unused budget when the campaign is closed
+		/// The campaign is managed through `RewardOrigin`
+		#[pallet::weight(T::WeightInfo::create_treasury_campaign())]
+		#[transactional]
+		pub fn create_treasury_campaign(
+			origin: OriginFor<T>,
+			campaign_ref: Vec<u8>,
+			#[pallet::compact] value: BalanceOf<T>,
+			start_block: T::BlockNumber,
+			end_block: T::BlockNumber,
+			asset: RewardAssetOf<T>,
+			badge_supply: Option<u32>,
+		) -> DispatchResult {
+			T::TreasuryOrigin::ensure_origin(origin)?;
+
+			Self::do_create_campaign(
+				T::TreasuryAccount::get(),
+				campaign_ref,
+				value,
+				start_block,
+				end_block,
+				asset,
+				badge_supply,
+			)
+		}
+
+		/// Start a `Draft` campaign once its `start_block` has been reached
+		/// The first payment from then also starts it
+		/// Lifecycle calls can only be made by an owner of the campaign or `RewardOrigin`
+		#[pallet::weight(T::WeightInfo::start_campaign())]
+		pub fn start_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= campaign.start_block, Error::<T>::CampaignNotStarted);
+
+			Self::change_status(campaign_id, &mut campaign, CampaignStatus::Active)
+		}
+
+		/// Pause an `Active` campaign, no user can be rewarded until it is resumed
+		#[pallet::weight(T::WeightInfo::pause_campaign())]
+		pub fn pause_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+
+			Self::change_status(campaign_id, &mut campaign, CampaignStatus::Paused)
+		}
+
+		/// Resume a `Paused` campaign
+		#[pallet::weight(T::WeightInfo::resume_campaign())]
+		pub fn resume_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+
+			Self::change_status(campaign_id, &mut campaign, CampaignStatus::Active)
+		}
+
+		/// End an `Active` or `Paused` campaign
+		/// Users can still be rewarded for what they did before the end, until `end_block`
+		#[pallet::weight(T::WeightInfo::end_campaign())]
+		pub fn end_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+
+			Self::change_status(campaign_id, &mut campaign, CampaignStatus::Ended)
+		}
+
+		/// Settle an `Ended` campaign once every user has been rewarded
+		/// Unused budget is refunded and bond released to the client
+		#[pallet::weight(T::WeightInfo::settle_campaign())]
+		pub fn settle_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			T::RewardOrigin::ensure_origin(origin)?;
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+
+			Self::close_campaign(campaign_id, &mut campaign, CampaignStatus::Settled)
+		}
+
+		/// Cancel a campaign that has not ended yet
+		/// Owners can only cancel before any user has been rewarded, `RewardOrigin` at any time
+		/// Unused budget is refunded and bond released to the client
+		#[pallet::weight(T::WeightInfo::cancel_campaign())]
+		pub fn cancel_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			if !Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)? {
+				ensure!(campaign.spent.is_zero(), Error::<T>::CampaignAlreadyPaid);
+			}
+
+			Self::close_campaign(campaign_id, &mut campaign, CampaignStatus::Cancelled)
+		}
+
+		/// Slash `amount` of the bond of a campaign found to be fraudulent
+		/// The amount goes to `beneficiary`, e.g. an affected user, or to `Slash` if `None`
+		/// The bond is held until the campaign is settled or cancelled, so it can be slashed
+		/// until then
+		#[pallet::weight(T::WeightInfo::slash_bond())]
+		pub fn slash_bond(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			#[pallet::compact] amount: BalanceOf<T>,
+			beneficiary: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::RewardOrigin::ensure_origin(origin)?;
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			ensure!(!amount.is_zero() && amount <= campaign.bond, Error::<T>::InsufficientBond);
+
+			let amount = match &beneficiary {
+				Some(who) => {
+					let missing = T::Currency::repatriate_reserved(
+						&campaign.client,
+						who,
+						amount,
+						BalanceStatus::Free,
+					)?;
+					amount.saturating_sub(missing)
+				},
+				None => {
+					let (imbalance, _) = T::Currency::slash_reserved(&campaign.client, amount);
+					let slashed = imbalance.peek();
+					T::Slash::on_unbalanced(imbalance);
+					slashed
+				},
+			};
+			campaign.bond = campaign.bond.saturating_sub(amount);
+			Campaigns::<T>::insert(campaign_id, &campaign);
+
+			Self::deposit_event(Event::BondSlashed {
+				campaign_id,
+				client: campaign.client,
+				amount,
+				beneficiary,
+			});
+
+			Ok(())
+		}
+
+		/// Add a task to a campaign, each of its completions is rewarded with `reward`
+		/// Users can be rewarded for it with `payment` until `deadline`, at most
+		/// `max_completions` of them
+		/// Only an owner of the campaign or `RewardOrigin` can add tasks, before the campaign ends
+		#[pallet::weight(T::WeightInfo::add_task())]
+		pub fn add_task(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			kind: TaskKind,
+			#[pallet::compact] reward: BalanceOf<T>,
+			max_completions: u32,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(campaign.status_at(now).is_editable(), Error::<T>::CampaignNotEditable);
+			ensure!(max_completions > 0 && deadline >= now, Error::<T>::InvalidTask);
+
+			let task_id = Self::next_task_id(campaign_id);
+			ensure!(task_id < T::MaxTasks::get(), Error::<T>::TooManyTasks);
+			Tasks::<T>::insert(
+				campaign_id,
+				task_id,
+				TaskInfo { kind, reward, max_completions, completions: 0, deadline },
+			);
+			NextTaskId::<T>::insert(campaign_id, task_id.saturating_add(1));
+
+			Self::deposit_event(Event::TaskAdded {
+				campaign_id,
+				task_id,
+				kind,
+				reward,
+				max_completions,
+				deadline,
+			});
+
+			Ok(())
+		}
+
+		/// Reward for all users with specific campaigns
 		/// Check deposit amount is enough balance to pay for all users
-		#[pallet::weight(10_000)]
+		/// Only an operator or owner of the campaign, or `RewardOrigin`, can reward its users
+		/// No user can be rewarded after `end_block`
+		/// Users of a campaign with tasks are rewarded for completing `task_id`, with the reward
+		/// advertised by the task
+		/// `batch_id` identifies the payment within the campaign, a retry of a batch already paid
+		/// rewards nobody
+		#[pallet::weight(T::WeightInfo::payment(users.len() as u32)
+			.max(T::WeightInfo::payment_vested(users.len() as u32)))]
 		#[transactional]
 		pub fn payment(
 			origin: OriginFor<T>,
-			campaign_index: CampaignIndex,
+			campaign_id: CampaignId,
+			batch_id: BatchId,
+			task_id: Option<TaskId>,
 			users: Vec<T::AccountId>,
 			#[pallet::compact] amount: BalanceOf<T>,
 		) -> DispatchResult {
-			// T::RewardOrigin::ensure_origin(origin)?;
-			let caller = ensure_signed(origin)?;
-			if !Self::only_admin(caller) {
-				return Err(Error::<T>::PermissionDeny)?;
-			}
+			let users: BoundedVec<T::AccountId, T::MaxUsersPerPayment> =
+				users.try_into().map_err(|_| Error::<T>::TooManyUsers)?;
 
 			//Ensure this campaign is registered
-			ensure!(Campaigns::<T>::contains_key(&campaign_index), Error::<T>::CampaignNotExist);
-
-			let campaign = Campaigns::<T>::get(&campaign_index).unwrap();
-			let total_amount = amount
-				.checked_mul(&users.len().saturated_into())
-				.ok_or(ArithmeticError::Overflow)?;
-			ensure!(total_amount <= campaign.value, Error::<T>::NotEnoughBalanceForUsers);
-			//let budget_remain = Self::remain_balance();
-			if let Some(p) = Self::campaigns(&campaign_index) {
-				let _ = T::Currency::unreserve(&p.client, p.bond);
-				for user in users.iter() {
-					let now = <frame_system::Pallet<T>>::block_number();
-					<BalanceUser<T>>::mutate(&user, |val| {
-						val.1 = val.1.saturating_add(amount);
-						val.0 = now;
-					});
-				}
+			let mut campaign =
+				Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Operator)?;
 
-				Self::deposit_event(Event::Payment { campaign_index, account: users });
+			if PaidBatches::<T>::get(campaign_id, batch_id) {
+				Self::deposit_event(Event::BatchAlreadyPaid { campaign_id, batch_id });
+				return Ok(())
+			}
+			PaidBatches::<T>::insert(campaign_id, batch_id, true);
+
+			// Users already rewarded for the task are skipped, whatever batch paid them
+			let (users, duplicates) = Self::split_rewarded(campaign_id, task_id, users);
+			if !duplicates.is_empty() {
+				Self::deposit_event(Event::DuplicatesSkipped {
+					campaign_id,
+					task_id,
+					account: duplicates,
+				});
+			}
+			if users.is_empty() {
+				return Ok(())
 			}
+			Self::reward_users(campaign_id, &mut campaign, task_id, &users, amount)?;
+
+			Self::deposit_event(Event::Payment { campaign_id, task_id, account: users });
+
+			Ok(())
+		}
+
+		/// Submit the hash of a proof that the caller completed a task of a campaign
+		/// The submission waits for an operator to approve or reject it, a rejected one can be
+		/// submitted again
+		/// Users already rewarded for the task can not submit, a pending submission is approved
+		/// when its user is rewarded for the task by `payment`
+		#[pallet::weight(T::WeightInfo::submit_proof())]
+		pub fn submit_proof(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			proof: T::Hash,
+		) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(campaign.status_at(now).is_payable(), Error::<T>::CampaignNotPayable);
+			let task = Tasks::<T>::get(campaign_id, task_id).ok_or(Error::<T>::TaskNotExist)?;
+			ensure!(now <= campaign.end_block, Error::<T>::CampaignExpired);
+			ensure!(now <= task.deadline, Error::<T>::TaskExpired);
+			ensure!(!Self::rewarded((campaign_id, task_id), &user), Error::<T>::AlreadyRewarded);
+			ensure!(
+				Submissions::<T>::get((campaign_id, task_id), &user)
+					.map_or(true, |submission| submission.status == SubmissionStatus::Rejected),
+				Error::<T>::SubmissionAlreadyExist
+			);
+
+			Submissions::<T>::insert(
+				(campaign_id, task_id),
+				&user,
+				Submission { proof, status: SubmissionStatus::Pending, submitted_at: now },
+			);
+			PendingSubmissions::<T>::insert((campaign_id, task_id), &user, true);
+			Self::deposit_event(Event::ProofSubmitted { campaign_id, task_id, user, proof });
+
+			Ok(())
+		}
+
+		/// Approve the pending submission of `user`, who is rewarded with the task reward
+		/// Allowed to the same origins as `payment`
+		#[pallet::weight(T::WeightInfo::approve_submission())]
+		#[transactional]
+		pub fn approve_submission(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			user: T::AccountId,
+		) -> DispatchResult {
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Operator)?;
+			let submission = Self::pending_submission(campaign_id, task_id, &user)?;
+			ensure!(!Self::rewarded((campaign_id, task_id), &user), Error::<T>::AlreadyRewarded);
+			let amount =
+				Tasks::<T>::get(campaign_id, task_id).ok_or(Error::<T>::TaskNotExist)?.reward;
+
+			Self::reward_users(
+				campaign_id,
+				&mut campaign,
+				Some(task_id),
+				sp_std::slice::from_ref(&user),
+				amount,
+			)?;
+			Self::deposit_event(Event::SubmissionApproved {
+				campaign_id,
+				task_id,
+				user,
+				proof: submission.proof,
+				amount,
+			});
+
+			Ok(())
+		}
+
+		/// Reject the pending submission of `user`
+		/// Allowed to the same origins as `payment`
+		#[pallet::weight(T::WeightInfo::reject_submission())]
+		pub fn reject_submission(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			task_id: TaskId,
+			user: T::AccountId,
+		) -> DispatchResult {
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Operator)?;
+			let submission = Self::pending_submission(campaign_id, task_id, &user)?;
+
+			submission.status = SubmissionStatus::Rejected;
+			Submissions::<T>::insert((campaign_id, task_id), &user, &submission);
+			PendingSubmissions::<T>::remove((campaign_id, task_id), &user);
+			Self::deposit_event(Event::SubmissionRejected {
+				campaign_id,
+				task_id,
+				user,
+				proof: submission.proof,
+			});
+
+			Ok(())
+		}
+
+		/// Publish the rewards of a campaign as the Merkle root of `leaves` leaves
+		/// `total` is taken from the campaign budget right away, users then claim their own
+		/// leaf with `claim_distribution`, so the size of the campaign does not matter
+		/// Not allowed on vesting campaigns, claims would be paid without following the schedule
+		/// Allowed to the same origins as `payment`
+		#[pallet::weight(T::WeightInfo::publish_distribution())]
+		pub fn publish_distribution(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			root: T::Hash,
+			#[pallet::compact] total: BalanceOf<T>,
+			leaves: u32,
+		) -> DispatchResult {
+			let mut campaign =
+				Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Operator)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(campaign.status_at(now).is_payable(), Error::<T>::CampaignNotPayable);
+			ensure!(now <= campaign.end_block, Error::<T>::CampaignExpired);
+			// Distribution claims are paid right away, they would bypass the vesting schedule
+			ensure!(
+				!CampaignVesting::<T>::contains_key(campaign_id),
+				Error::<T>::DistributionWithVesting
+			);
+			ensure!(
+				!Distributions::<T>::contains_key(campaign_id),
+				Error::<T>::DistributionAlreadyExist
+			);
+			ensure!(total <= campaign.remaining(), Error::<T>::NotEnoughBalanceForUsers);
+			campaign.spent = campaign.spent.saturating_add(total);
+			Campaigns::<T>::insert(campaign_id, &campaign);
+
+			Distributions::<T>::insert(
+				campaign_id,
+				Distribution { root, total, claimed: Zero::zero(), leaves },
+			);
+			Self::deposit_event(Event::DistributionPublished { campaign_id, root, total });
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		/// Claim the leaf `index` of a campaign distribution, paid to the caller right away
+		/// `proof` is the list of sibling hashes from the leaf up to the root
+		/// Published rewards can still be claimed once the campaign is closed
+		#[pallet::weight(T::WeightInfo::claim_distribution(proof.len() as u32))]
+		#[transactional]
+		pub fn claim_distribution(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			index: u32,
+			#[pallet::compact] amount: BalanceOf<T>,
+			proof: Vec<T::Hash>,
+		) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			ensure!(proof.len() <= T::MaxProofLength::get() as usize, Error::<T>::InvalidProof);
+
+			let campaign = Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			let mut distribution =
+				Distributions::<T>::get(campaign_id).ok_or(Error::<T>::DistributionNotExist)?;
+			ensure!(index < distribution.leaves, Error::<T>::InvalidProof);
+
+			let (word, bit) = (index / 32, 1u32 << (index % 32));
+			let claimed_bits = ClaimedBitmap::<T>::get(campaign_id, word);
+			ensure!(claimed_bits & bit == 0, Error::<T>::AlreadyClaimed);
+
+			let leaf = Self::distribution_leaf(index, &user, amount);
+			ensure!(Self::verify_proof(distribution.root, leaf, &proof), Error::<T>::InvalidProof);
+
+			// A malformed tree can not pay more than was published
+			distribution.claimed = distribution.claimed.saturating_add(amount);
+			ensure!(
+				distribution.claimed <= distribution.total,
+				Error::<T>::NotEnoughBalanceForUsers
+			);
+			Distributions::<T>::insert(campaign_id, distribution);
+			ClaimedBitmap::<T>::insert(campaign_id, word, claimed_bits | bit);
+
+			Self::transfer_out(campaign.asset, &user, amount, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::DistributionClaimed { campaign_id, index, user, amount });
+
+			Ok(())
+		}
+
+		/// Claim reward on behalf of `user`
+		/// Used by admin to relay the claim of custodial users
+		#[pallet::weight(T::WeightInfo::claim())]
 		pub fn claim(
 			origin: OriginFor<T>,
+			campaign_id: CampaignId,
 			#[pallet::compact] amount: BalanceOf<T>,
 			user: T::AccountId,
 		) -> DispatchResult {
-			let caller = ensure_signed(origin)?;
-			if !Self::only_admin(caller) {
-				return Err(Error::<T>::PermissionDeny)?;
+			T::RewardOrigin::ensure_origin(origin)?;
+			let asset = Self::make_transfer(&user, campaign_id, amount)?;
+			Self::deposit_event(Event::Claim { user, campaign_id, asset, amount });
+			Ok(())
+		}
+
+		/// Claim reward of the caller from a campaign
+		/// Reward can only be claimed `ClaimDuration` blocks after the user was last rewarded by
+		/// this campaign
+		#[pallet::weight(T::WeightInfo::claim_reward())]
+		pub fn claim_reward(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			let asset = Self::make_transfer(&user, campaign_id, amount)?;
+			Self::deposit_event(Event::Claim { user, campaign_id, asset, amount });
+			Ok(())
+		}
+
+		/// Claim the whole reward of the caller from each campaign it can be claimed from
+		/// At most `limit` campaigns of the caller are looked at
+		#[pallet::weight(T::WeightInfo::claim_all(*limit))]
+		#[transactional]
+		pub fn claim_all(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let claimable: Vec<(CampaignId, RewardEntryOf<T>)> = Rewards::<T>::iter_prefix(&user)
+				.take(limit as usize)
+				.filter(|(_, reward)| {
+					now >= reward.credited_at.saturating_add(T::ClaimDuration::get())
+				})
+				.collect();
+			ensure!(!claimable.is_empty(), Error::<T>::NothingToClaim);
+
+			for (campaign_id, reward) in claimable {
+				let amount = reward.unclaimed();
+				Rewards::<T>::remove(&user, campaign_id);
+				Self::transfer_out(reward.asset, &user, amount, ExistenceRequirement::KeepAlive)?;
+				Self::deposit_event(Event::Claim {
+					user: user.clone(),
+					campaign_id,
+					asset: reward.asset,
+					amount,
+				});
 			}
-			// T::RewardOrigin::ensure_origin(origin)?;
-			let _ = Self::make_transfer(&user, amount)?;
-			Self::deposit_event(Event::Claim { user });
+
 			Ok(())
 		}
 
-		/// add admin for special purposes
-		#[pallet::weight(10_000)]
-		pub fn add_admin(origin: OriginFor<T>, user: T::AccountId) -> DispatchResult {
-			let caller = ensure_signed(origin)?;
+		/// Release the rewards of a campaign following `schedule`, or right away if `None`
+		/// The schedule can only change before any user is rewarded
+		/// Users of a vesting campaign claim with `claim_vested`, rewards are not paid
+		/// automatically
+		#[pallet::weight(T::WeightInfo::set_vesting())]
+		pub fn set_vesting(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			schedule: Option<VestingScheduleOf<T>>,
+		) -> DispatchResult {
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(campaign.status_at(now).is_editable(), Error::<T>::CampaignNotEditable);
+			ensure!(campaign.spent.is_zero(), Error::<T>::VestingAlreadyStarted);
 
-			if !Self::only_admin(caller) {
-				return Err(Error::<T>::PermissionDeny)?;
+			CampaignVesting::<T>::set(campaign_id, schedule);
+			match schedule {
+				Some(schedule) => Self::deposit_event(Event::VestingSet { campaign_id, schedule }),
+				None => Self::deposit_event(Event::VestingRemoved { campaign_id }),
 			}
 
+			Ok(())
+		}
+
+		/// Claim the released part of the vesting rewards of the caller in `asset`
+		#[pallet::weight(T::WeightInfo::claim_vested(T::MaxVestingPositions::get()))]
+		#[transactional]
+		pub fn claim_vested(origin: OriginFor<T>, asset: RewardAssetOf<T>) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let mut amount = BalanceOf::<T>::zero();
+			VestingPositions::<T>::mutate_exists(&user, asset, |positions| {
+				if let Some(list) = positions {
+					for position in list.iter_mut() {
+						let claimable = position.claimable(now);
+						position.claimed = position.claimed.saturating_add(claimable);
+						amount = amount.saturating_add(claimable);
+					}
+					list.retain(|position| position.claimed < position.total);
+					if list.is_empty() {
+						*positions = None;
+					}
+				}
+			});
+			ensure!(!amount.is_zero(), Error::<T>::NothingVested);
+
+			Self::transfer_out(asset, &user, amount, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::VestedClaimed { user, asset, amount });
+
+			Ok(())
+		}
+
+		/// Appoint `who` to `role` in a campaign, replacing the role it had
+		/// Only an owner of the campaign or `RewardOrigin` can manage roles
+		#[pallet::weight(T::WeightInfo::grant_role())]
+		pub fn grant_role(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			who: T::AccountId,
+			role: CampaignRole,
+		) -> DispatchResult {
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+			ensure!(who != campaign.client, Error::<T>::ClientRoleFixed);
+
+			CampaignRoles::<T>::insert(campaign_id, &who, role);
+			Self::deposit_event(Event::RoleGranted { campaign_id, who, role });
+
+			Ok(())
+		}
+
+		/// Remove the role of `who` in a campaign
+		#[pallet::weight(T::WeightInfo::revoke_role())]
+		pub fn revoke_role(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			who: T::AccountId,
+		) -> DispatchResult {
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotExist)?;
+			Self::ensure_campaign_role(origin, campaign_id, &campaign, CampaignRole::Owner)?;
+			ensure!(who != campaign.client, Error::<T>::ClientRoleFixed);
+
+			let role =
+				CampaignRoles::<T>::take(campaign_id, &who).ok_or(Error::<T>::RoleNotExist)?;
+			Self::deposit_event(Event::RoleRevoked { campaign_id, who, role });
+
+			Ok(())
+		}
+
+		/// add admin for special purposes
+		#[pallet::weight(T::WeightInfo::add_admin())]
+		pub fn add_admin(origin: OriginFor<T>, user: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Self::only_admin(user.clone()), Error::<T>::AlreadyAdmin);
+			let count = Self::admin_count().saturating_add(1);
+			ensure!(count <= T::MaxAdmins::get(), Error::<T>::TooManyAdmins);
+
 			Admins::<T>::insert(&user, true);
+			AdminCount::<T>::put(count);
 			Self::deposit_event(Event::AddAdmin { user });
 			Ok(())
 		}
 
 		/// remove admin for special purposes
-		#[pallet::weight(10_000)]
+		/// At least `MinAdmins` are kept, and an admin can not remove itself, it hands over its
+		/// seat instead
+		#[pallet::weight(T::WeightInfo::remove_admin())]
 		pub fn remove_admin(origin: OriginFor<T>, user: T::AccountId) -> DispatchResult {
-			let caller = ensure_signed(origin)?;
-			if !Self::only_admin(caller) {
-				return Err(Error::<T>::PermissionDeny)?;
-			}
+			let caller = ensure_signed(origin.clone()).ok();
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(caller.as_ref() != Some(&user), Error::<T>::CannotRemoveSelf);
+			ensure!(Self::only_admin(user.clone()), Error::<T>::NotAdmin);
+			let count = Self::admin_count().saturating_sub(1);
+			ensure!(count >= T::MinAdmins::get(), Error::<T>::TooFewAdmins);
+
 			Admins::<T>::remove(&user);
+			AdminHandovers::<T>::remove(&user);
+			AdminCount::<T>::put(count);
 			Self::deposit_event(Event::RemoveAdmin { user });
 
 			Ok(())
 		}
+
+		/// Propose `to` to take over the admin seat of the caller
+		/// The seat only changes hands once `to` accepts it with `accept_admin_handover`, a new
+		/// proposal replaces the previous one
+		#[pallet::weight(T::WeightInfo::propose_admin_handover())]
+		pub fn propose_admin_handover(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			ensure!(Self::only_admin(from.clone()), Error::<T>::NotAdmin);
+			ensure!(!Self::only_admin(to.clone()), Error::<T>::AlreadyAdmin);
+
+			AdminHandovers::<T>::insert(&from, &to);
+			Self::deposit_event(Event::AdminHandoverProposed { from, to });
+
+			Ok(())
+		}
+
+		/// Accept the admin seat proposed by `from`, who is no longer an admin afterwards
+		#[pallet::weight(T::WeightInfo::accept_admin_handover())]
+		pub fn accept_admin_handover(origin: OriginFor<T>, from: T::AccountId) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			ensure!(
+				AdminHandovers::<T>::get(&from).as_ref() == Some(&to),
+				Error::<T>::HandoverNotExist
+			);
+			ensure!(Self::only_admin(from.clone()), Error::<T>::NotAdmin);
+			ensure!(!Self::only_admin(to.clone()), Error::<T>::AlreadyAdmin);
+
+			AdminHandovers::<T>::remove(&from);
+			Admins::<T>::remove(&from);
+			Admins::<T>::insert(&to, true);
+			Self::deposit_event(Event::AdminHandedOver { from, to });
+
+			Ok(())
+		}
+
+		/// Replace the whole admin list, to recover when admins are lost
+		/// Pending handovers are dropped
+		#[pallet::weight(T::WeightInfo::force_set_admins(admins.len() as u32))]
+		pub fn force_set_admins(
+			origin: OriginFor<T>,
+			mut admins: Vec<T::AccountId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			admins.sort();
+			admins.dedup();
+			let count = admins.len() as u32;
+			ensure!(count >= T::MinAdmins::get(), Error::<T>::TooFewAdmins);
+			ensure!(count <= T::MaxAdmins::get(), Error::<T>::TooManyAdmins);
+
+			let _ = Admins::<T>::remove_all(None);
+			let _ = AdminHandovers::<T>::remove_all(None);
+			for admin in admins.iter() {
+				Admins::<T>::insert(admin, true);
+			}
+			AdminCount::<T>::put(count);
+			Self::deposit_event(Event::AdminsForceSet { count });
+
+			Ok(())
+		}
+	}
+}
+
+/// Ensure the origin is signed by an account of `Admins`
+pub struct EnsureAdmin<T>(PhantomData<T>);
+
+impl<T: Config> EnsureOrigin<T::Origin> for EnsureAdmin<T> {
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Pallet::<T>::only_admin(who.clone()) => Ok(who),
+			r => Err(T::Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> T::Origin {
+		let who: T::AccountId = frame_benchmarking::whitelisted_caller();
+		Admins::<T>::insert(&who, true);
+		T::Origin::from(frame_system::RawOrigin::Signed(who))
 	}
 }
 
@@ -292,28 +1768,603 @@ impl<T: Config> Pallet<T> {
 		Admins::<T>::get(user).unwrap_or(false)
 	}
 
+	/// Role of `who` in a campaign, its client is always the owner
+	pub fn role_of(
+		campaign_id: CampaignId,
+		campaign: &CampaignOf<T>,
+		who: &T::AccountId,
+	) -> Option<CampaignRole> {
+		if &campaign.client == who {
+			return Some(CampaignRole::Owner)
+		}
+		CampaignRoles::<T>::get(campaign_id, who)
+	}
+
+	/// Ensure `origin` is `RewardOrigin` or an account whose role in the campaign includes
+	/// `required`
+	/// Returns `true` for `RewardOrigin`
+	fn ensure_campaign_role(
+		origin: T::Origin,
+		campaign_id: CampaignId,
+		campaign: &CampaignOf<T>,
+		required: CampaignRole,
+	) -> Result<bool, DispatchError> {
+		match T::RewardOrigin::try_origin(origin) {
+			Ok(_) => Ok(true),
+			Err(origin) => {
+				let who = ensure_signed(origin)?;
+				ensure!(
+					Self::role_of(campaign_id, campaign, &who)
+						.map_or(false, |role| role.includes(required)),
+					Error::<T>::PermissionDeny
+				);
+				Ok(false)
+			},
+		}
+	}
+
+	/// Submission of `user` for a task, if it is waiting for review
+	fn pending_submission(
+		campaign_id: CampaignId,
+		task_id: TaskId,
+		user: &T::AccountId,
+	) -> Result<SubmissionOf<T>, DispatchError> {
+		let submission = Submissions::<T>::get((campaign_id, task_id), user)
+			.ok_or(Error::<T>::SubmissionNotExist)?;
+		ensure!(submission.status == SubmissionStatus::Pending, Error::<T>::SubmissionNotPending);
+		Ok(submission)
+	}
+
+	/// Reward `users` of a campaign with `amount` each, for completing `task_id` if any
+	#[transactional]
+	fn reward_users(
+		campaign_id: CampaignId,
+		campaign: &mut CampaignOf<T>,
+		task_id: Option<TaskId>,
+		users: &[T::AccountId],
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::update_status(campaign_id, campaign)?;
+		ensure!(campaign.status.is_payable(), Error::<T>::CampaignNotPayable);
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(now <= campaign.end_block, Error::<T>::CampaignExpired);
+		match task_id {
+			Some(task_id) => {
+				let mut task =
+					Tasks::<T>::get(campaign_id, task_id).ok_or(Error::<T>::TaskNotExist)?;
+				ensure!(amount == task.reward, Error::<T>::RewardMismatch);
+				ensure!(now <= task.deadline, Error::<T>::TaskExpired);
+				task.completions = task
+					.completions
+					.checked_add(users.len().saturated_into())
+					.filter(|completions| *completions <= task.max_completions)
+					.ok_or(Error::<T>::TaskCompletionsExceeded)?;
+				Tasks::<T>::insert(campaign_id, task_id, task);
+			},
+			None => ensure!(Self::next_task_id(campaign_id) == 0, Error::<T>::TaskRequired),
+		}
+		let total_amount = amount
+			.checked_mul(&users.len().saturated_into())
+			.ok_or(ArithmeticError::Overflow)?;
+		// Only the budget left in this campaign can be used, not the whole campaign account
+		ensure!(total_amount <= campaign.remaining(), Error::<T>::NotEnoughBalanceForUsers);
+		campaign.spent = campaign.spent.saturating_add(total_amount);
+
+		if let Some(badge) = campaign.badge.as_mut() {
+			let minted = badge
+				.minted
+				.checked_add(users.len().saturated_into())
+				.filter(|minted| *minted <= badge.max_supply)
+				.ok_or(Error::<T>::BadgeSupplyExceeded)?;
+			// The owner of the class reserves the badge deposits, the client pays them when the
+			// pallet owns it
+			if <T::Uniques as nonfungibles::Inspect<T::AccountId>>::class_owner(&badge.class) ==
+				Some(Self::account_id())
+			{
+				let deposit = T::BadgeDeposit::get().saturating_mul(users.len().saturated_into());
+				T::Currency::transfer(
+					&campaign.client,
+					&Self::account_id(),
+					deposit,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			for (instance, user) in (badge.minted..minted).zip(users.iter()) {
+				T::Uniques::mint_into(&badge.class, &instance.into(), user)?;
+			}
+			badge.minted = minted;
+		}
+		Campaigns::<T>::insert(campaign_id, &*campaign);
+		if let Some(task_id) = task_id {
+			for user in users.iter() {
+				Rewarded::<T>::insert((campaign_id, task_id), user, true);
+				// A pending submission of the user is approved by the reward, whatever paid it
+				if PendingSubmissions::<T>::take((campaign_id, task_id), user) {
+					Submissions::<T>::mutate((campaign_id, task_id), user, |submission| {
+						if let Some(submission) = submission {
+							submission.status = SubmissionStatus::Approved;
+						}
+					});
+				}
+			}
+		}
+
+		// Nothing to queue when users are only rewarded with a badge
+		if amount.is_zero() {
+			return Ok(())
+		}
+		// Vesting rewards are claimed by the users as they are released
+		// A user vesting `MaxVestingPositions` rewards already gets the reward added to the latest
+		// one, and released on its schedule, rather than failing the whole payment
+		if let Some(schedule) = Self::campaign_vesting(campaign_id) {
+			for user in users.iter() {
+				VestingPositions::<T>::try_mutate(user, campaign.asset, |positions| {
+					let position = VestingPosition {
+						total: amount,
+						claimed: Zero::zero(),
+						start: now,
+						schedule,
+					};
+					if positions.try_push(position).is_ok() {
+						return Ok(())
+					}
+					let latest = positions.last_mut().ok_or(Error::<T>::TooManyVestingPositions)?;
+					latest.total = latest.total.saturating_add(amount);
+					Ok::<_, Error<T>>(())
+				})?;
+			}
+			return Ok(())
+		}
+
+		// Bounded per campaign, so one campaign can not hold back the payouts of the others
+		let pending = PendingPayouts::<T>::get(campaign_id)
+			.checked_add(users.len().saturated_into())
+			.filter(|pending| *pending <= T::MaxPendingPayouts::get())
+			.ok_or(Error::<T>::PayoutQueueFull)?;
+		PendingPayouts::<T>::insert(campaign_id, pending);
+		let mut tail = PayoutQueueTail::<T>::get();
+		let payout_at = now.saturating_add(T::PayoutDuration::get());
+		for user in users.iter() {
+			Rewards::<T>::mutate(user, campaign_id, |entry| {
+				let reward = entry.get_or_insert_with(|| RewardEntry {
+					asset: campaign.asset,
+					amount: Zero::zero(),
+					claimed: Zero::zero(),
+					credited_at: now,
+				});
+				reward.amount = reward.amount.saturating_add(amount);
+				reward.credited_at = now;
+			});
+			PayoutQueue::<T>::insert(tail, (user.clone(), campaign_id, payout_at, 0));
+			tail = tail.wrapping_add(1);
+		}
+		PayoutQueueTail::<T>::put(tail);
+
+		Ok(())
+	}
+
+	/// Split `users` into those not rewarded yet for `task_id` of a campaign and the duplicates,
+	/// which were rewarded for the task before or are repeated in `users`
+	fn split_rewarded(
+		campaign_id: CampaignId,
+		task_id: Option<TaskId>,
+		users: BoundedVec<T::AccountId, T::MaxUsersPerPayment>,
+	) -> (
+		BoundedVec<T::AccountId, T::MaxUsersPerPayment>,
+		BoundedVec<T::AccountId, T::MaxUsersPerPayment>,
+	) {
+		let mut seen = BTreeSet::new();
+		let (fresh, duplicates): (Vec<_>, Vec<_>) =
+			users.into_inner().into_iter().partition(|user| {
+				!task_id.map_or(false, |task_id| Self::rewarded((campaign_id, task_id), user)) &&
+					seen.insert(user.clone())
+			});
+		// both are subsets of `users`, so within its bound
+		(fresh.try_into().unwrap_or_default(), duplicates.try_into().unwrap_or_default())
+	}
+
+	/// Move `campaign` to `next` status if the lifecycle allows it and store it
+	/// The transitions due at its blocks are made first, they may already reach `next`
+	fn change_status(
+		campaign_id: CampaignId,
+		campaign: &mut CampaignOf<T>,
+		next: CampaignStatus,
+	) -> DispatchResult {
+		let due = campaign.status_at(<frame_system::Pallet<T>>::block_number());
+		ensure!(
+			(due != campaign.status && due == next) || due.can_transition_to(next),
+			Error::<T>::InvalidStatusTransition
+		);
+		Self::update_status(campaign_id, campaign)?;
+		if campaign.status == next {
+			return Ok(())
+		}
+		Self::set_status(campaign_id, campaign, next)
+	}
+
+	/// Make the transitions of `campaign` due at `start_block` and `end_block`, see
+	/// `Campaign::status_at`
+	fn update_status(campaign_id: CampaignId, campaign: &mut CampaignOf<T>) -> DispatchResult {
+		let due = campaign.status_at(<frame_system::Pallet<T>>::block_number());
+		if campaign.status == CampaignStatus::Draft && due != CampaignStatus::Draft {
+			Self::set_status(campaign_id, campaign, CampaignStatus::Active)?;
+		}
+		if campaign.status != due {
+			Self::set_status(campaign_id, campaign, due)?;
+		}
+		Ok(())
+	}
+
+	/// Move `campaign` to `next` status if the lifecycle allows it, store it and emit the event
+	fn set_status(
+		campaign_id: CampaignId,
+		campaign: &mut CampaignOf<T>,
+		next: CampaignStatus,
+	) -> DispatchResult {
+		ensure!(campaign.status.can_transition_to(next), Error::<T>::InvalidStatusTransition);
+		let from = campaign.status;
+		campaign.status = next;
+		Campaigns::<T>::insert(campaign_id, &*campaign);
+
+		Self::deposit_event(Event::CampaignStatusChanged { campaign_id, from, to: next });
+		Ok(())
+	}
+
+	/// Close `campaign` with `status`, refund the unused budget and release the bond
+	#[transactional]
+	fn close_campaign(
+		campaign_id: CampaignId,
+		campaign: &mut CampaignOf<T>,
+		status: CampaignStatus,
+	) -> DispatchResult {
+		let refunded = campaign.remaining();
+		let bond = sp_std::mem::replace(&mut campaign.bond, Zero::zero());
+		Self::change_status(campaign_id, campaign, status)?;
+
+		if !refunded.is_zero() {
+			Self::transfer_out(
+				campaign.asset,
+				&campaign.client,
+				refunded,
+				ExistenceRequirement::AllowDeath,
+			)?;
+		}
+		if !bond.is_zero() {
+			let _ = T::Currency::unreserve(&campaign.client, bond);
+			Self::deposit_event(Event::BondReleased {
+				campaign_id,
+				client: campaign.client.clone(),
+				amount: bond,
+			});
+		}
+
+		Self::deposit_event(Event::CampaignClosed { campaign_id, refunded, bond });
+		Ok(())
+	}
+
+	/// Take the first badge class id from `NextBadgeClass` that is not created in `Uniques`
+	/// yet, at most `MAX_BADGE_CLASS_ATTEMPTS` ids are tried
+	fn take_badge_class(campaign_id: CampaignId) -> Result<T::ClassId, DispatchError> {
+		let mut class = NextBadgeClass::<T>::get()
+			.unwrap_or_else(|| T::BadgeClassStart::get().saturating_add(campaign_id.into()));
+		for _ in 0..MAX_BADGE_CLASS_ATTEMPTS {
+			let next = class.checked_add(&One::one()).ok_or(ArithmeticError::Overflow)?;
+			if <T::Uniques as nonfungibles::Inspect<T::AccountId>>::class_owner(&class).is_none() {
+				NextBadgeClass::<T>::put(next);
+				return Ok(class)
+			}
+			class = next;
+		}
+		Err(Error::<T>::BadgeClassUnavailable.into())
+	}
+
+	/// Create a campaign of `client`, see `create_campaign`
+	pub(crate) fn do_create_campaign(
+		client: T::AccountId,
+		campaign_ref: Vec<u8>,
+		value: BalanceOf<T>,
+		start_block: T::BlockNumber,
+		end_block: T::BlockNumber,
+		asset: RewardAssetOf<T>,
+		badge_supply: Option<u32>,
+	) -> DispatchResult {
+		let campaign_ref: CampaignRefOf<T> =
+			campaign_ref.try_into().map_err(|_| Error::<T>::CampaignRefTooLong)?;
+		ensure!(!CampaignRefs::<T>::contains_key(&campaign_ref), Error::<T>::CampaignAlreadyExist);
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(start_block < end_block && now < end_block, Error::<T>::InvalidCampaignPeriod);
+		let status =
+			if start_block <= now { CampaignStatus::Active } else { CampaignStatus::Draft };
+
+		let campaign_id = Self::next_campaign_id();
+		let next_id = campaign_id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+		// `value` of an asset campaign is not in native currency, only the minimum is bonded
+		let bond = match asset {
+			RewardAsset::Native =>
+				(T::CampaignDepositMinimum::get()).max(T::CampaignDeposit::get() * value),
+			RewardAsset::Asset(_) => T::CampaignDepositMinimum::get(),
+		};
+		// Reserved balance for client
+		let _ = T::Currency::reserve(&client, bond)?;
+
+		let badge = match badge_supply {
+			Some(max_supply) => {
+				ensure!(max_supply > 0, Error::<T>::InvalidBadgeSupply);
+				let class = Self::take_badge_class(campaign_id)?;
+				// The pallet reserves the class deposit, it is paid by the client
+				T::Currency::transfer(
+					&client,
+					&Self::account_id(),
+					T::BadgeClassDeposit::get(),
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::Uniques::create_class(&class, &Self::account_id(), &Self::account_id())?;
+				Some(Badge { class, max_supply, minted: 0 })
+			},
+			None => None,
+		};
+
+		Campaigns::<T>::insert(
+			campaign_id,
+			Campaign {
+				client: client.clone(),
+				value,
+				bond,
+				spent: Zero::zero(),
+				status,
+				start_block,
+				end_block,
+				asset,
+				badge,
+			},
+		);
+		CampaignRefs::<T>::insert(&campaign_ref, campaign_id);
+		NextCampaignId::<T>::put(next_id);
+
+		Self::deposit_campaign_account(&client, campaign_id)?;
+
+		Self::deposit_event(Event::NewCampaign { campaign_id, campaign_ref, status });
+
+		Ok(())
+	}
+
 	#[transactional]
 	pub fn deposit_campaign_account(
 		sender: &T::AccountId,
-		campaign_index: CampaignIndex,
+		campaign_id: CampaignId,
 	) -> Result<(), DispatchError> {
-		let campaign = Campaigns::<T>::get(&campaign_index).unwrap();
+		let campaign = Campaigns::<T>::get(campaign_id).unwrap();
 		let value = campaign.value;
+		if value.is_zero() {
+			return Ok(())
+		}
 
-		let imbalance = T::Currency::withdraw(
-			&sender,
-			value,
-			WithdrawReasons::TRANSFER,
-			ExistenceRequirement::KeepAlive,
-		)?;
+		// the treasury funding a campaign would pay the fee to itself
+		let fee = if *sender == T::TreasuryAccount::get() {
+			Zero::zero()
+		} else {
+			T::PlatformFee::get() * value
+		};
+		match campaign.asset {
+			RewardAsset::Native => {
+				let imbalance = T::Currency::withdraw(
+					&sender,
+					value.saturating_add(fee),
+					WithdrawReasons::TRANSFER,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				let (deposit, fee_imbalance) = imbalance.split(value);
 
-		T::Currency::resolve_creating(&Self::account_id(), imbalance);
+				T::Currency::resolve_creating(&Self::account_id(), deposit);
+				T::FeeDestination::on_unbalanced(fee_imbalance);
+			},
+			RewardAsset::Asset(id) => {
+				// the first campaign in an asset also pays its minimum balance, kept by the
+				// campaign account so that payouts never leave it dust to sweep to a user
+				let account = Self::account_id();
+				let minimum = if T::Assets::balance(id, &account).is_zero() {
+					T::Assets::minimum_balance(id)
+				} else {
+					Zero::zero()
+				};
+				let amount = value.saturating_add(minimum).saturating_add(fee);
+				T::Assets::transfer(id, sender, &account, amount, true)?;
+				Self::pay_asset_fee(id, fee)?;
+			},
+		}
 		//Deposit into campaign account
 
-		Self::deposit_event(Event::DepositClient { campaign_index, deposit_amount: value });
+		Self::deposit_event(Event::DepositClient {
+			campaign_id,
+			asset: campaign.asset,
+			deposit_amount: value,
+			fee,
+		});
+		Ok(())
+	}
+
+	/// Add `fee`, held by the campaign account, to the platform fees owed in asset `id` and pay
+	/// them to `TreasuryAccount` once it can receive them: when they reach the minimum balance of
+	/// the asset or the treasury already holds some
+	fn pay_asset_fee(id: T::AssetId, fee: BalanceOf<T>) -> DispatchResult {
+		if fee.is_zero() {
+			return Ok(())
+		}
+		let treasury = T::TreasuryAccount::get();
+		let due = PendingFees::<T>::get(id).saturating_add(fee);
+		if due < T::Assets::minimum_balance(id) && T::Assets::balance(id, &treasury).is_zero() {
+			PendingFees::<T>::insert(id, due);
+			return Ok(())
+		}
+		T::Assets::transfer(id, &Self::account_id(), &treasury, due, true)?;
+		PendingFees::<T>::remove(id);
 		Ok(())
 	}
 
+	/// Pay due entries of `PayoutQueue`, within `remaining_weight` and `MaxPayoutsPerBlock`
+	/// Return the weight used
+	pub(crate) fn process_payout_queue(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// queue entry, pending count of the campaign, user balance and both accounts of the
+		// transfer, a failed payout is pushed back at the tail until `MAX_PAYOUT_ATTEMPTS`
+		let payout_weight = db_weight.reads_writes(5, 6);
+		// head and tail, head is written back at the end
+		let mut used_weight = db_weight.reads_writes(2, 1);
+		if used_weight > remaining_weight {
+			return 0
+		}
+
+		let mut head = PayoutQueueHead::<T>::get();
+		// retried entries are pushed after `tail`, they are not processed again in this block
+		let tail = PayoutQueueTail::<T>::get();
+		let mut retry_at = tail;
+		let mut paid = 0u32;
+		while head != tail &&
+			paid < T::MaxPayoutsPerBlock::get() &&
+			used_weight.saturating_add(payout_weight) <= remaining_weight
+		{
+			used_weight = used_weight.saturating_add(payout_weight);
+			if let Some((user, campaign_id, payout_at, failed)) = PayoutQueue::<T>::get(head) {
+				// entries are queued in order, nothing after this one is due yet
+				if payout_at > now {
+					break
+				}
+				PayoutQueue::<T>::remove(head);
+				if Self::auto_payout(&user, campaign_id, now) {
+					PendingPayouts::<T>::mutate(campaign_id, |pending| {
+						*pending = pending.saturating_sub(1)
+					});
+				} else if failed.saturating_add(1) < MAX_PAYOUT_ATTEMPTS {
+					let retry = now.saturating_add(T::PayoutDuration::get());
+					PayoutQueue::<T>::insert(retry_at, (user, campaign_id, retry, failed + 1));
+					retry_at = retry_at.wrapping_add(1);
+				} else {
+					// the reward stays in `Rewards`, only the queue entry is dropped
+					PendingPayouts::<T>::mutate(campaign_id, |pending| {
+						*pending = pending.saturating_sub(1)
+					});
+					Self::deposit_event(Event::AutoPayoutAbandoned { user, campaign_id });
+				}
+			}
+			head = head.wrapping_add(1);
+			paid += 1;
+		}
+		PayoutQueueHead::<T>::put(head);
+		PayoutQueueTail::<T>::put(retry_at);
+
+		used_weight
+	}
+
+	/// Pay the whole reward of `user` from a campaign if it has not been withdrawn for
+	/// `PayoutDuration`
+	/// Return false if the transfer failed and the payout should be retried later
+	fn auto_payout(user: &T::AccountId, campaign_id: CampaignId, now: T::BlockNumber) -> bool {
+		let reward = match Self::rewards(user, campaign_id) {
+			Some(reward) => reward,
+			// already claimed
+			None => return true,
+		};
+		// rewarded again since then, a later entry will pay it
+		if now < reward.credited_at.saturating_add(T::PayoutDuration::get()) {
+			return true
+		}
+
+		let (asset, amount) = (reward.asset, reward.unclaimed());
+		match Self::transfer_out(asset, user, amount, ExistenceRequirement::KeepAlive) {
+			Ok(_) => {
+				Rewards::<T>::remove(user, campaign_id);
+				Self::deposit_event(Event::AutoPaid {
+					user: user.clone(),
+					campaign_id,
+					asset,
+					amount,
+				});
+				true
+			},
+			Err(e) => {
+				log::warn!(target: "runtime::task", "automatic payout failed: {:?}", e);
+				false
+			},
+		}
+	}
+
+	/// Leaf of a distribution rewarding `amount` to `account` at `index`
+	pub fn distribution_leaf(index: u32, account: &T::AccountId, amount: BalanceOf<T>) -> T::Hash {
+		T::Hashing::hash_of(&(index, account, amount))
+	}
+
+	/// Root reached from `leaf` through `proof`, each pair of nodes is hashed in sorted order
+	pub fn compute_root(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+		proof.iter().fold(leaf, |node, sibling| {
+			if node <= *sibling {
+				T::Hashing::hash_of(&(node, sibling))
+			} else {
+				T::Hashing::hash_of(&(sibling, node))
+			}
+		})
+	}
+
+	/// Check `proof` links `leaf` to `root`
+	fn verify_proof(root: T::Hash, leaf: T::Hash, proof: &[T::Hash]) -> bool {
+		Self::compute_root(leaf, proof) == root
+	}
+
+	/// Campaign with the status it is in at the current block, see `Campaign::status_at`
+	pub fn campaign_info(campaign_id: CampaignId) -> Option<CampaignOf<T>> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Self::campaigns(campaign_id).map(|mut campaign| {
+			campaign.status = campaign.status_at(now);
+			campaign
+		})
+	}
+
+	/// Id of the campaign created with `campaign_ref`
+	pub fn campaign_id_of(campaign_ref: Vec<u8>) -> Option<CampaignId> {
+		let campaign_ref: CampaignRefOf<T> = campaign_ref.try_into().ok()?;
+		Self::campaign_of_ref(campaign_ref)
+	}
+
+	/// Budget of a campaign that can still be rewarded
+	pub fn remaining_budget(campaign_id: CampaignId) -> Option<BalanceOf<T>> {
+		Self::campaigns(campaign_id).map(|campaign| campaign.remaining())
+	}
+
+	/// Unclaimed rewards of `who` in `asset` from all campaigns, and the block from which all of
+	/// them can be claimed
+	pub fn claimable(
+		who: &T::AccountId,
+		asset: RewardAssetOf<T>,
+	) -> (BalanceOf<T>, T::BlockNumber) {
+		Rewards::<T>::iter_prefix_values(who)
+			.filter(|reward| reward.asset == asset)
+			.fold((Zero::zero(), Zero::zero()), |(amount, when), reward| {
+				(
+					amount.saturating_add(reward.unclaimed()),
+					when.max(reward.credited_at.saturating_add(T::ClaimDuration::get())),
+				)
+			})
+	}
+
+	/// Vesting rewards of `who` in `asset`: the amount that can be claimed now and the amount
+	/// still locked
+	pub fn vesting_balance(
+		who: &T::AccountId,
+		asset: RewardAssetOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Self::vesting_positions(who, asset).iter().fold(
+			(Zero::zero(), Zero::zero()),
+			|(claimable, locked): (BalanceOf<T>, BalanceOf<T>), position| {
+				(
+					claimable.saturating_add(position.claimable(now)),
+					locked.saturating_add(position.total.saturating_sub(position.vested(now))),
+				)
+			},
+		)
+	}
+
 	/// Remaining balance of campaign account
 	pub fn remain_balance() -> BalanceOf<T> {
 		let account = Self::account_id();
@@ -321,29 +2372,47 @@ impl<T: Config> Pallet<T> {
 		T::Currency::free_balance(&account).saturating_sub(T::Currency::minimum_balance())
 	}
 
-	#[transactional]
-	fn make_transfer(to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
-		//ensure!(CampaignPayout::<T>::get(&index).contains(to), Error::<T>::UserNotReward);
+	/// Transfer `amount` of `asset` from the campaign account to `to`
+	fn transfer_out(
+		asset: RewardAssetOf<T>,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+		existence: ExistenceRequirement,
+	) -> DispatchResult {
 		let campaign_account = Self::account_id();
-		let (when, balance_user) = Self::balance_of(&to);
-		ensure!(balance_user >= amount.clone(), Error::<T>::CanNotClaim);
-		let now = <frame_system::Pallet<T>>::block_number();
-		let duration = T::ClaimDuration::get();
-
-		ensure!(now >= when.saturating_add(duration), Error::<T>::InvalidClaim);
-		if balance_user == amount {
-			<BalanceUser<T>>::insert(to, (now, BalanceOf::<T>::zero()));
-		} else {
-			log::info!("balance_user > amount");
-			<BalanceUser<T>>::mutate(to, |val| {
-				//val.unwrap().1 -= amount
-				val.1 = val.1.saturating_sub(amount);
-			});
+		match asset {
+			RewardAsset::Native => T::Currency::transfer(&campaign_account, to, amount, existence),
+			// campaign account keeps the minimum balance of each asset it was funded with
+			RewardAsset::Asset(id) =>
+				T::Assets::transfer(id, &campaign_account, to, amount, true).map(|_| ()),
 		}
+	}
 
-		let _ =
-			T::Currency::transfer(&campaign_account, to, amount, ExistenceRequirement::KeepAlive)?;
+	/// Pay `amount` of the reward of `to` from a campaign, return the asset it is paid in
+	#[transactional]
+	fn make_transfer(
+		to: &T::AccountId,
+		campaign_id: CampaignId,
+		amount: BalanceOf<T>,
+	) -> Result<RewardAssetOf<T>, DispatchError> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let asset = Rewards::<T>::try_mutate_exists(to, campaign_id, |entry| {
+			let reward = entry.as_mut().ok_or(Error::<T>::CanNotClaim)?;
+			ensure!(reward.unclaimed() >= amount, Error::<T>::CanNotClaim);
+			ensure!(
+				now >= reward.credited_at.saturating_add(T::ClaimDuration::get()),
+				Error::<T>::InvalidClaim
+			);
+			reward.claimed = reward.claimed.saturating_add(amount);
+			let asset = reward.asset;
+			if reward.unclaimed().is_zero() {
+				*entry = None;
+			}
+			Ok::<_, DispatchError>(asset)
+		})?;
 
-		Ok(())
+		Self::transfer_out(asset, to, amount, ExistenceRequirement::KeepAlive)?;
+
+		Ok(asset)
 	}
 }
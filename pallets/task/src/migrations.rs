@@ -0,0 +1,314 @@
+//! Storage migrations for the task pallet.
+
+use super::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::{
+	storage::migration::{remove_storage_prefix, storage_iter, storage_key_iter},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+	ReversibleStorageHasher,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+
+/// Migrate from the initial layout, where campaigns were keyed by their reference and users had
+/// a single native balance.
+///
+/// Only campaigns are migrated here, each one is bonded so there are few of them. User balances
+/// are left to the multi-block migration of `v3`.
+pub mod v1 {
+	use super::*;
+
+	/// Campaign as stored before lifecycle, budget tracking and numeric ids.
+	#[derive(Decode)]
+	pub struct OldCampaign<AccountId, Balance> {
+		pub client: AccountId,
+		pub value: Balance,
+		pub bond: Balance,
+	}
+
+	type OldCampaignOf<T> = OldCampaign<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain >= 1 {
+				log::info!(target: "runtime::task", "skipping v1 migration, already {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			let now = frame_system::Pallet::<T>::block_number();
+			let (mut reads, mut writes) = (1u64, 0u64);
+
+			// What old campaigns paid is unknown, their budget is considered used: what they did
+			// not pay stays in the pallet account and is not refunded. They are `Ended`, so an
+			// admin can settle them to release the bond
+			// The old `payment` unreserved the bond without clearing it, so bonds are taken from
+			// what each client still has reserved, in storage order, instead of trusting them
+			let mut reserved: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
+			let campaigns: Vec<(Vec<u8>, OldCampaignOf<T>)> =
+				storage_key_iter::<Vec<u8>, OldCampaignOf<T>, Twox64Concat>(pallet, b"Campaigns")
+					.drain()
+					.collect();
+			let mut next_id: CampaignId = 0;
+			for (campaign_ref, old) in campaigns {
+				let campaign_id = next_id;
+				next_id = next_id.saturating_add(1);
+				let campaign_ref: Result<CampaignRefOf<T>, _> = campaign_ref.try_into();
+				match campaign_ref {
+					Ok(campaign_ref) => {
+						CampaignRefs::<T>::insert(&campaign_ref, campaign_id);
+						writes += 1;
+					},
+					Err(_) => log::warn!(
+						target: "runtime::task",
+						"reference of campaign {} is too long, dropped",
+						campaign_id
+					),
+				}
+				let left = reserved.entry(old.client.clone()).or_insert_with(|| {
+					reads += 1;
+					T::Currency::reserved_balance(&old.client)
+				});
+				let bond = old.bond.min(*left);
+				*left = left.saturating_sub(bond);
+				Campaigns::<T>::insert(
+					campaign_id,
+					Campaign {
+						client: old.client,
+						value: old.value,
+						bond,
+						spent: old.value,
+						status: CampaignStatus::Ended,
+						start_block: Zero::zero(),
+						end_block: now,
+						asset: RewardAsset::Native,
+						badge: None,
+					},
+				);
+				reads += 1;
+				writes += 2;
+			}
+			NextCampaignId::<T>::put(next_id);
+
+			// User balances are left in their initial layout, `v3` moves them to `Rewards` over
+			// several blocks
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "runtime::task", "migrated {} campaigns to v1", next_id);
+
+			T::DbWeight::get().reads_writes(reads + 1, writes + 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			let count = if Pallet::<T>::on_chain_storage_version() < 1 {
+				let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+				let campaigns = storage_key_iter::<Vec<u8>, OldCampaignOf<T>, Twox64Concat>(
+					pallet,
+					b"Campaigns",
+				)
+				.count() as u32;
+				Some(campaigns)
+			} else {
+				None
+			};
+			Self::set_temp_storage(count, "count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 1, "storage version not updated");
+			let count: Option<u32> =
+				Self::get_temp_storage("count").ok_or("count of pre_upgrade not found")?;
+			if let Some(campaigns) = count {
+				ensure!(NextCampaignId::<T>::get() == campaigns, "campaign ids not assigned");
+				ensure!(
+					Campaigns::<T>::iter().count() as u32 == campaigns,
+					"campaigns not migrated"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Count the existing admins, so that the last ones can not be removed.
+pub mod v2 {
+	use super::*;
+
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain >= 2 {
+				log::info!(target: "runtime::task", "skipping v2 migration, already {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let count = Admins::<T>::iter().count() as u32;
+			AdminCount::<T>::put(count);
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target: "runtime::task", "counted {} admins in v2", count);
+
+			T::DbWeight::get().reads_writes(count as u64 + 1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "storage version not updated");
+			ensure!(
+				Admins::<T>::iter().count() as u32 == AdminCount::<T>::get(),
+				"admin count does not match"
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Replace the balance of each user in each asset by a reward entry per campaign.
+///
+/// Balances credited before can not be attributed to a campaign, so they are kept as rewards of
+/// `LEGACY_CAMPAIGN` and the payout queue, which pointed to them, is emptied.
+pub mod v3 {
+	use super::*;
+
+	type OldBalanceOf<T> = (<T as frame_system::Config>::BlockNumber, BalanceOf<T>);
+
+	/// Key of a balance in `BalanceUser`, under the pallet and storage prefixes
+	#[cfg(test)]
+	pub(crate) fn balance_key<T: Config>(user: &T::AccountId, asset: &RewardAssetOf<T>) -> Vec<u8> {
+		use frame_support::StorageHasher;
+		let mut key = Twox64Concat::hash(&user.encode());
+		key.extend(Twox64Concat::hash(&asset.encode()));
+		key
+	}
+
+	/// User and asset of a balance from its key in `BalanceUser`
+	/// Keys of the initial layout only hold the user, their balance is native
+	fn decode_balance_key<T: Config>(key: &[u8]) -> Option<(T::AccountId, RewardAssetOf<T>)> {
+		let mut rest = Twox64Concat::reverse(key);
+		let user = T::AccountId::decode(&mut rest).ok()?;
+		if rest.is_empty() {
+			return Some((user, RewardAsset::Native))
+		}
+		let mut rest = Twox64Concat::reverse(rest);
+		let asset = RewardAssetOf::<T>::decode(&mut rest).ok()?;
+		Some((user, asset))
+	}
+
+	/// User balances moved to `Rewards` at most per block
+	pub const BALANCES_PER_BLOCK: u32 = 1_000;
+
+	/// Campaign user balances are moved under, as rewards the users claim as usual
+	/// A user with balances in several assets gets the campaigns counted down from it
+	pub const LEGACY_CAMPAIGN: CampaignId = CampaignId::MAX;
+
+	/// Move user balances from `BalanceUser` to `Rewards` while `LegacyBalancesLeft` is set,
+	/// within `remaining_weight` and `BALANCES_PER_BLOCK`
+	/// Return the weight used
+	pub(crate) fn migrate_balances<T: Config>(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// the flag, and the balances left looked up at the end
+		let mut used_weight = db_weight.reads_writes(2, 1);
+		if used_weight > remaining_weight {
+			return 0
+		}
+		if !LegacyBalancesLeft::<T>::get() {
+			return db_weight.reads(1)
+		}
+
+		// the balance and the reward of the user under `LEGACY_CAMPAIGN`
+		let balance_weight = db_weight.reads_writes(2, 2);
+		let limit = remaining_weight
+			.saturating_sub(used_weight)
+			.checked_div(balance_weight)
+			.unwrap_or(Weight::MAX)
+			.min(BALANCES_PER_BLOCK.into());
+
+		let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+		let balances: Vec<(Vec<u8>, OldBalanceOf<T>)> =
+			storage_iter::<OldBalanceOf<T>>(pallet, b"BalanceUser")
+				.drain()
+				.take(limit as usize)
+				.collect();
+		let mut moved = 0u32;
+		for (key, (credited_at, amount)) in balances {
+			used_weight = used_weight.saturating_add(balance_weight);
+			let (user, asset) = match decode_balance_key::<T>(&key) {
+				Some(decoded) => decoded,
+				None => {
+					log::warn!(target: "runtime::task", "undecodable user balance, dropped");
+					continue
+				},
+			};
+			if amount.is_zero() {
+				continue
+			}
+			let mut campaign_id = LEGACY_CAMPAIGN;
+			while Rewards::<T>::contains_key(&user, campaign_id) {
+				used_weight = used_weight.saturating_add(db_weight.reads(1));
+				campaign_id = campaign_id.saturating_sub(1);
+			}
+			Rewards::<T>::insert(
+				&user,
+				campaign_id,
+				RewardEntry { asset, amount, claimed: Zero::zero(), credited_at },
+			);
+			moved += 1;
+		}
+		log::info!(target: "runtime::task", "moved {} user balances to rewards", moved);
+
+		if storage_iter::<OldBalanceOf<T>>(pallet, b"BalanceUser").next().is_none() {
+			LegacyBalancesLeft::<T>::kill();
+			log::info!(target: "runtime::task", "all user balances moved to rewards");
+		}
+
+		used_weight
+	}
+
+	/// Replace user balances by rewards under `LEGACY_CAMPAIGN` and clear the payout queue
+	/// Balances are not paid out, the pallet account keeps holding them until users claim
+	/// Only the first `BALANCES_PER_BLOCK` are moved here, the rest in `on_idle` of the next
+	/// blocks
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain >= 3 {
+				log::info!(target: "runtime::task", "skipping v3 migration, already {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			// entries of the old queue refer to balances, the new queue starts empty
+			remove_storage_prefix(pallet, b"PayoutQueue", &[]);
+			PayoutQueueHead::<T>::kill();
+			PayoutQueueTail::<T>::kill();
+
+			LegacyBalancesLeft::<T>::put(true);
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			let weight = migrate_balances::<T>(Weight::MAX);
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 5))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 3, "storage version not updated");
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			ensure!(
+				LegacyBalancesLeft::<T>::get() ||
+					storage_iter::<OldBalanceOf<T>>(pallet, b"BalanceUser").next().is_none(),
+				"user balances left unmigrated"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,18 +1,23 @@
 use crate as pallet_task;
-use frame_support::{parameter_types, PalletId};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32, EnsureOneOf},
+	PalletId,
+};
 use frame_system as system;
 use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
-	AccountId32, Permill,
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup, Verify},
+	AccountId32, MultiSignature, Permill,
 };
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 pub type Balance = u128;
 pub type AccountId = AccountId32;
+pub type Extrinsic = TestXt<Call, ()>;
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -22,6 +27,8 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Config<T>,  Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 		Task: pallet_task::{Pallet, Call,  Storage, Event<T>},
 	}
 );
@@ -74,12 +81,98 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+impl pallet_uniques::Config for Test {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ConstU128<2>;
+	type InstanceDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type AttributeDepositBase = ConstU128<1>;
+	type DepositPerByte = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type KeyLimit = ConstU32<50>;
+	type ValueLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const CampaignDepositMinimum: Balance = 1000;
 	pub const CampaignDeposit : Permill = Permill::from_percent(2);
+	pub static PlatformFee: Permill = Permill::zero();
 	pub const ClaimDuration : u64 = 10;
 	pub const PayoutDuration: u64 = 20;
 	pub const TaskPalletId: PalletId = PalletId(*b"plt/task");
+	pub const MaxCampaignRefLength: u32 = 36;
+	pub const MaxPendingPayouts: u32 = 10;
+	pub const MaxPayoutsPerBlock: u32 = 2;
+	pub const BadgeClassStart: u32 = 100;
+	pub const BadgeClassDeposit: Balance = 2;
+	pub const BadgeDeposit: Balance = 1;
+	pub const MaxUsersPerPayment: u32 = 16;
+	pub const MaxProofLength: u32 = 8;
+	pub const MaxTasks: u32 = 2;
+	pub const MaxVerificationsPerBlock: u32 = 2;
+	pub const MaxVestingPositions: u32 = 2;
+	pub const MinAdmins: u32 = 1;
+	pub const MaxAdmins: u32 = 8;
+	pub const TreasuryAccount: AccountId32 = TREASURY;
+}
+
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<<MultiSignature as Verify>::Signer, MultiSignature>
+	for TestAuthId
+{
+	type RuntimeAppPublic = pallet_task::AuthorityId;
+	type GenericSignature = sp_core::sr25519::Signature;
+	type GenericPublic = sp_core::sr25519::Public;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <MultiSignature as Verify>::Signer;
+	type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: <MultiSignature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
 }
 
 impl pallet_task::Config for Test {
@@ -87,10 +180,36 @@ impl pallet_task::Config for Test {
 	type Currency = Balances;
 	type CampaignDepositMinimum = CampaignDepositMinimum;
 	type CampaignDeposit = CampaignDeposit;
-	// type RewardOrigin = EnsureRoot<AccountId>;
+	type Slash = ();
+	type PlatformFee = PlatformFee;
+	type FeeDestination = ();
+	type TreasuryOrigin = EnsureRoot<AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type RewardOrigin = EnsureOneOf<EnsureRoot<AccountId>, pallet_task::EnsureAdmin<Test>>;
+	type AdminOrigin = EnsureOneOf<EnsureRoot<AccountId>, pallet_task::EnsureAdmin<Test>>;
 	type ClaimDuration = ClaimDuration;
 	type PayoutDuration = PayoutDuration;
 	type PalletId = TaskPalletId;
+	type MaxCampaignRefLength = MaxCampaignRefLength;
+	type MaxPendingPayouts = MaxPendingPayouts;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type MaxUsersPerPayment = MaxUsersPerPayment;
+	type MaxProofLength = MaxProofLength;
+	type MaxTasks = MaxTasks;
+	type MaxVestingPositions = MaxVestingPositions;
+	type AuthorityId = TestAuthId;
+	type MaxVerificationsPerBlock = MaxVerificationsPerBlock;
+	type MinAdmins = MinAdmins;
+	type MaxAdmins = MaxAdmins;
+	type AssetId = u32;
+	type Assets = Assets;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Uniques = Uniques;
+	type BadgeClassStart = BadgeClassStart;
+	type BadgeClassDeposit = BadgeClassDeposit;
+	type BadgeDeposit = BadgeDeposit;
+	type WeightInfo = ();
 }
 
 pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
@@ -100,6 +219,9 @@ pub const USER1: AccountId32 = AccountId32::new([3u8; 32]);
 pub const USER2: AccountId32 = AccountId32::new([4u8; 32]);
 pub const USER3: AccountId32 = AccountId32::new([5u8; 32]);
 
+pub const ADMIN: AccountId32 = AccountId32::new([9u8; 32]);
+pub const TREASURY: AccountId32 = AccountId32::new([8u8; 32]);
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
@@ -107,5 +229,10 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 100000), (BOB, 100000)] }
 		.assimilate_storage(&mut t)
 		.unwrap();
-	t.into()
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		pallet_task::Admins::<Test>::insert(ADMIN, true);
+		pallet_task::AdminCount::<Test>::put(1);
+	});
+	ext
 }
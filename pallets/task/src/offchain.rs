@@ -0,0 +1,140 @@
+//! Offchain worker verifying the pending submissions against an HTTP endpoint.
+//!
+//! The endpoint is read from the persistent offchain local storage under [`ENDPOINT_KEY`], e.g.
+//! set with the `offchain_localStorageSet` RPC. Nothing is verified while it is not set.
+//!
+//! For every pending submission the worker sends
+//! `GET <endpoint>?campaign_id=<id>&task_id=<id>&user=0x<account>&proof=0x<hash>`, where the
+//! account and hash are hex SCALE encoded, and expects `true` or `false` as body. The verdict is
+//! sent on chain as a signed `approve_submission` or `reject_submission`, using the first key of
+//! type [`KEY_TYPE`](crate::KEY_TYPE) in the keystore, so its account must be allowed by
+//! `RewardOrigin`.
+
+extern crate alloc;
+
+use super::*;
+use alloc::format;
+use frame_system::offchain::{SendSignedTransaction, Signer};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::offchain::{
+	http,
+	storage::{MutateStorageError, StorageValueRef},
+	Duration, StorageKind,
+};
+
+/// Key of the verification endpoint in the persistent offchain local storage
+pub const ENDPOINT_KEY: &[u8] = b"task::verification-endpoint";
+
+/// Prefix of the block a submission verdict has last been sent at
+const SENT_PREFIX: &[u8] = b"task::verification-sent";
+
+/// Time given to the endpoint to answer, in milliseconds
+const FETCH_TIMEOUT: u64 = 3_000;
+
+/// Blocks after which a verdict that has not been included is sent again
+const RESEND_INTERVAL: u32 = 10;
+
+impl<T: Config> Pallet<T> {
+	/// Verify up to `MaxVerificationsPerBlock` pending submissions and send their verdict
+	pub(crate) fn verify_submissions(now: T::BlockNumber) -> Result<(), &'static str> {
+		let endpoint = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, ENDPOINT_KEY)
+			.ok_or("verification endpoint not set")?;
+		let endpoint =
+			sp_std::str::from_utf8(&endpoint).map_err(|_| "verification endpoint not utf8")?;
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return Err("no verification key in the keystore")
+		}
+
+		// Only the pending submissions are iterated, not the decided ones
+		let pending = PendingSubmissions::<T>::iter_keys()
+			.filter_map(|(key, user)| {
+				Submissions::<T>::get(key, &user).map(|submission| (key, user, submission))
+			})
+			.filter(|((campaign_id, task_id), user, _)| {
+				Self::should_send(*campaign_id, *task_id, user, now)
+			})
+			.take(T::MaxVerificationsPerBlock::get() as usize);
+		for ((campaign_id, task_id), user, submission) in pending {
+			let verdict =
+				Self::fetch_verdict(endpoint, campaign_id, task_id, &user, &submission.proof);
+			let approved = match verdict {
+				Ok(approved) => approved,
+				Err(e) => {
+					log::warn!(
+						target: "runtime::task",
+						"verification of campaign {} task {} failed: {:?}",
+						campaign_id,
+						task_id,
+						e
+					);
+					continue
+				},
+			};
+
+			let result = signer.send_signed_transaction(|_| {
+				if approved {
+					Call::approve_submission { campaign_id, task_id, user: user.clone() }
+				} else {
+					Call::reject_submission { campaign_id, task_id, user: user.clone() }
+				}
+			});
+			if !matches!(result, Some((_, Ok(())))) {
+				log::warn!(target: "runtime::task", "failed to send verification transaction");
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Record that the verdict of a submission is sent at `now`, unless it has been sent less
+	/// than `RESEND_INTERVAL` blocks ago
+	fn should_send(
+		campaign_id: CampaignId,
+		task_id: TaskId,
+		user: &T::AccountId,
+		now: T::BlockNumber,
+	) -> bool {
+		let key = (SENT_PREFIX, campaign_id, task_id, user).encode();
+		let sent = StorageValueRef::persistent(&key);
+		let result = sent.mutate(|last: Result<Option<T::BlockNumber>, _>| match last {
+			Ok(Some(at)) if now < at.saturating_add(RESEND_INTERVAL.into()) => Err(()),
+			_ => Ok(now),
+		});
+		!matches!(result, Err(MutateStorageError::ValueFunctionFailed(())))
+	}
+
+	/// Ask the endpoint if `user` completed a task, with `proof`
+	fn fetch_verdict(
+		endpoint: &str,
+		campaign_id: CampaignId,
+		task_id: TaskId,
+		user: &T::AccountId,
+		proof: &T::Hash,
+	) -> Result<bool, http::Error> {
+		let url = format!(
+			"{}?campaign_id={}&task_id={}&user=0x{}&proof=0x{}",
+			endpoint,
+			campaign_id,
+			task_id,
+			HexDisplay::from(&user.encode()),
+			HexDisplay::from(&proof.encode()),
+		);
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT));
+		let pending = http::Request::get(&url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+		if response.code != 200 {
+			return Err(http::Error::Unknown)
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		match sp_std::str::from_utf8(&body).map(|body| body.trim()) {
+			Ok("true") => Ok(true),
+			Ok("false") => Ok(false),
+			_ => Err(http::Error::Unknown),
+		}
+	}
+}
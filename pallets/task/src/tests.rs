@@ -1,9 +1,8 @@
 use super::*;
 use crate::{mock::*, Error};
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use pallet_balances::Error as BalancesError;
-use sp_runtime::traits::BadOrigin;
 // fn id_account(index: &Vec<u8>) -> AccountId {
 // 	<Test as Config>::PalletId::get().into_sub_account(index)
 // }
@@ -11,11 +10,19 @@ use sp_runtime::traits::BadOrigin;
 #[test]
 fn create_campaign_should_work() {
 	new_test_ext().execute_with(|| {
-		let campaign_id = "0".as_bytes().to_vec();
+		let campaign_id: CampaignId = 0;
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(BOB), campaign_id.clone(), 1000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
 
-		let campaign = Task::campaigns(&campaign_id).unwrap();
+		let campaign = Task::campaigns(campaign_id).unwrap();
 		//Check client
 		assert_eq!(campaign.client, BOB);
 		//Check value
@@ -35,49 +42,69 @@ fn create_campaign_should_work() {
 #[test]
 fn payment_should_be_working() {
 	new_test_ext().execute_with(|| {
-		let campaign_id_0 = "0".as_bytes().to_vec();
+		let campaign_id_0: CampaignId = 0;
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(BOB), campaign_id_0.clone(), 5000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
 		System::set_block_number(10);
 		assert_ok!(Task::payment(
-			Origin::root(),
-			campaign_id_0.clone(),
+			Origin::signed(ADMIN),
+			campaign_id_0,
+			0,
+			None,
 			vec![USER1, USER2],
 			1000u32.into()
 		));
-		//Check unreserved balance for BOB
-		assert_eq!(Balances::reserved_balance(BOB), 0);
+		//Check bond of BOB is held until the campaign is settled
+		assert_eq!(Balances::reserved_balance(BOB), 1000);
 		//Check balance of user 1
-		assert_eq!(Task::balance_of(USER1).1, 1000);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 1000);
 		//Check balance of user 2
-		assert_eq!(Task::balance_of(USER2).1, 1000);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 1000);
 	});
 }
 
 #[test]
 fn claim_should_be_working() {
 	new_test_ext().execute_with(|| {
-		let campaign_id_0 = "0".as_bytes().to_vec();
+		let campaign_id_0: CampaignId = 0;
 
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(BOB), campaign_id_0.clone(), 5000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
 		System::set_block_number(10);
 		assert_ok!(Task::payment(
-			Origin::root(),
-			campaign_id_0.clone(),
+			Origin::signed(ADMIN),
+			campaign_id_0,
+			0,
+			None,
 			vec![USER1, USER2],
 			1000u32.into()
 		));
 		//Check balance of user 1
-		assert_eq!(Task::balance_of(USER1).1, 1000);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 1000);
 		//Check balance of user 2
-		assert_eq!(Task::balance_of(USER2).1, 1000);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 1000);
 
 		System::set_block_number(25);
 		//Before user 1 claim
 		assert_eq!(Balances::free_balance(USER1), 0);
 		// User 1 claim
-		assert_ok!(Task::claim(Origin::root(), 1000, USER1));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id_0, 1000, USER1));
 
 		// //after user 1 claim
 		assert_eq!(Balances::free_balance(USER1), 1000);
@@ -86,26 +113,34 @@ fn claim_should_be_working() {
 		assert_eq!(Balances::free_balance(Task::account_id()), 4000);
 
 		// User 2 claim
-		assert_ok!(Task::claim(Origin::root(), 1000, USER2));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id_0, 1000, USER2));
 
 		//Remaining balance in system
 		assert_eq!(Balances::free_balance(Task::account_id()), 3000);
 
 		// Balance storage should be zero
-		assert_eq!(Task::balance_of(USER1).1, 0);
-		assert_eq!(Task::balance_of(USER2).1, 0);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 0);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 0);
 	});
 }
 
 #[test]
 fn multiple_campaigns_should_work() {
 	new_test_ext().execute_with(|| {
-		let campaign_id_0 = "0".as_bytes().to_vec();
-		let campaign_id_1 = "1".as_bytes().to_vec();
+		let campaign_id_0: CampaignId = 0;
+		let campaign_id_1: CampaignId = 1;
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(BOB), campaign_id_0.clone(), 1000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
 
-		let campaign = Task::campaigns(&campaign_id_0).unwrap();
+		let campaign = Task::campaigns(campaign_id_0).unwrap();
 		//Check client
 		assert_eq!(campaign.client, BOB);
 		//Check value
@@ -120,8 +155,16 @@ fn multiple_campaigns_should_work() {
 		assert_eq!(Balances::free_balance(Task::account_id()), 1000);
 
 		//Create campaign 2
-		assert_ok!(Task::create_campaign(Origin::signed(ALICE), campaign_id_1.clone(), 5000));
-		let campaign2 = Task::campaigns(&campaign_id_1).unwrap();
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		let campaign2 = Task::campaigns(campaign_id_1).unwrap();
 		//Check client balance should be reserve bond amount when deposi for campaign 2
 		//reserve amount = 1000.max(5000*permil(2)) = 1000
 		//(T::CampaignDepositMinimum::get()).max(T::CampaignDeposit::get() * value);
@@ -139,14 +182,18 @@ fn multiple_campaigns_should_work() {
 
 		System::set_block_number(10);
 		assert_ok!(Task::payment(
-			Origin::root(),
-			campaign_id_1.clone(),
+			Origin::signed(ADMIN),
+			campaign_id_1,
+			0,
+			None,
 			vec![USER1, USER2],
 			2500u32.into()
 		));
 		assert_ok!(Task::payment(
-			Origin::root(),
-			campaign_id_0.clone(),
+			Origin::signed(ADMIN),
+			campaign_id_0,
+			1,
+			None,
 			vec![USER1, USER2],
 			500u32.into()
 		));
@@ -155,16 +202,16 @@ fn multiple_campaigns_should_work() {
 		//Before user 1 claim
 		assert_eq!(Balances::free_balance(USER1), 0);
 		// User 1 claim for campaign 1
-		assert_ok!(Task::claim(Origin::root(), 500, USER1));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id_0, 500, USER1));
 		//after user 1 claim
 		assert_eq!(Balances::free_balance(USER1), 500);
 
 		assert_eq!(Balances::free_balance(Task::account_id()), 5500);
 
 		// User 2 claim for campaign 1
-		assert_ok!(Task::claim(Origin::root(), 499, USER2));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id_0, 499, USER2));
 		// User 1 claim for campaign 2
-		assert_ok!(Task::claim(Origin::root(), 1000, USER1));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id_1, 1000, USER1));
 		// //after user 1 claim for campaign 2
 		//500 + 1000
 		assert_eq!(Balances::free_balance(USER1), 1500);
@@ -174,12 +221,19 @@ fn multiple_campaigns_should_work() {
 #[test]
 fn can_not_create_campaign() {
 	new_test_ext().execute_with(|| {
-		let campaign_id = "0".as_bytes().to_vec();
 		// Dispatch a signed extrinsic.
 
 		// User have enough money to reserve some amount first -> can be deposit
 		assert_noop!(
-			Task::create_campaign(Origin::signed(USER1), campaign_id.clone(), 1000),
+			Task::create_campaign(
+				Origin::signed(USER1),
+				"0".as_bytes().to_vec(),
+				1000,
+				0,
+				100,
+				RewardAsset::Native,
+				None
+			),
 			BalancesError::<Test>::InsufficientBalance
 		);
 	})
@@ -189,28 +243,50 @@ fn can_not_create_campaign() {
 
 fn can_not_payment_for_user() {
 	new_test_ext().execute_with(|| {
-		let campaign_id = "0".as_bytes().to_vec();
+		let campaign_id: CampaignId = 0;
 		let users_reward = vec![USER2, USER3];
 		<Test as Config>::Currency::make_free_balance_be(&USER1, 2000u32.into());
 
-		// should be root signed
 		assert_noop!(
 			Task::payment(
-				Origin::signed(USER1),
-				campaign_id.clone(),
+				Origin::signed(ADMIN),
+				campaign_id,
+				0,
+				None,
 				users_reward.clone(),
 				100u32.into()
 			),
-			BadOrigin
+			Error::<Test>::CampaignNotExist
 		);
 
 		// total amount > deposit amount
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(USER1), campaign_id.clone(), 1000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(USER1),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		// should be signed by an admin or someone with a role in the campaign
+		assert_noop!(
+			Task::payment(
+				Origin::signed(USER3),
+				campaign_id,
+				1,
+				None,
+				users_reward.clone(),
+				100u32.into()
+			),
+			Error::<Test>::PermissionDeny
+		);
 
 		// 550 + 550 (reward amount) > 1000 (deposit amount)
 		assert_noop!(
-			Task::payment(Origin::root(), campaign_id.clone(), users_reward, 550u32.into()),
+			Task::payment(Origin::signed(ADMIN), campaign_id, 2, None, users_reward, 550u32.into()),
 			Error::<Test>::NotEnoughBalanceForUsers
 		);
 	})
@@ -219,27 +295,2290 @@ fn can_not_payment_for_user() {
 #[test]
 fn user_can_not_claim() {
 	new_test_ext().execute_with(|| {
-		let campaign_id = "0".as_bytes().to_vec();
+		let campaign_id: CampaignId = 0;
 		let users_reward = vec![USER2, USER3];
 		<Test as Config>::Currency::make_free_balance_be(&USER1, 2000u32.into());
 
 		// Dispatch a signed extrinsic.
-		assert_ok!(Task::create_campaign(Origin::signed(USER1), campaign_id.clone(), 1000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(USER1),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
 
 		// 300+300 (reward amount) < 1000 (deposit amount) -> valid
-		assert_ok!(Task::payment(Origin::root(), campaign_id.clone(), users_reward, 300u32.into()));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			users_reward,
+			300u32.into()
+		));
 		System::set_block_number(0);
-		// only root can call
-		assert_noop!(Task::claim(Origin::signed(BOB), 100, BOB), BadOrigin);
+		// only admin can call
+		assert_noop!(
+			Task::claim(Origin::signed(BOB), campaign_id, 100, BOB),
+			DispatchError::BadOrigin
+		);
 
-		assert_noop!(Task::claim(Origin::root(), 100, BOB), Error::<Test>::CanNotClaim);
+		assert_noop!(
+			Task::claim(Origin::signed(ADMIN), campaign_id, 100, BOB),
+			Error::<Test>::CanNotClaim
+		);
 
 		System::set_block_number(20);
-		assert_ok!(Task::claim(Origin::root(), 100, USER2));
-		assert_ok!(Task::claim(Origin::root(), 200, USER2));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id, 100, USER2));
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id, 200, USER2));
 		// can not claim anymore
-		assert_noop!(Task::claim(Origin::root(), 200, USER2), Error::<Test>::CanNotClaim);
+		assert_noop!(
+			Task::claim(Origin::signed(ADMIN), campaign_id, 200, USER2),
+			Error::<Test>::CanNotClaim
+		);
 
 		assert_eq!(<Test as Config>::Currency::free_balance(USER2), 300);
 	})
 }
+
+#[test]
+fn campaign_lifecycle_should_work() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		// start block in the future -> campaign stays in draft
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			10,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_eq!(Task::campaigns(campaign_id).unwrap().status, CampaignStatus::Draft);
+
+		// can not reward users of a draft campaign
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 100u32.into()),
+			Error::<Test>::CampaignNotPayable
+		);
+		assert_noop!(
+			Task::start_campaign(Origin::signed(BOB), campaign_id),
+			Error::<Test>::CampaignNotStarted
+		);
+
+		System::set_block_number(10);
+		// only client or admin can change the status
+		assert_noop!(
+			Task::start_campaign(Origin::signed(ALICE), campaign_id),
+			Error::<Test>::PermissionDeny
+		);
+		assert_ok!(Task::start_campaign(Origin::signed(BOB), campaign_id));
+		System::assert_last_event(
+			crate::Event::<Test>::CampaignStatusChanged {
+				campaign_id,
+				from: CampaignStatus::Draft,
+				to: CampaignStatus::Active,
+			}
+			.into(),
+		);
+
+		assert_ok!(Task::pause_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 1, None, vec![USER1], 100u32.into()),
+			Error::<Test>::CampaignNotPayable
+		);
+		assert_ok!(Task::resume_campaign(Origin::signed(BOB), campaign_id));
+		assert_ok!(Task::end_campaign(Origin::signed(BOB), campaign_id));
+
+		// rewards can still be paid after the campaign ended
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			2,
+			None,
+			vec![USER1],
+			100u32.into()
+		));
+
+		// only admin can settle
+		assert_noop!(
+			Task::settle_campaign(Origin::signed(BOB), campaign_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_eq!(Task::campaigns(campaign_id).unwrap().status, CampaignStatus::Settled);
+
+		// settled campaign can not move anymore
+		assert_noop!(
+			Task::resume_campaign(Origin::signed(ADMIN), campaign_id),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 3, None, vec![USER1], 100u32.into()),
+			Error::<Test>::CampaignNotPayable
+		);
+	});
+}
+
+#[test]
+fn can_not_reward_users_after_end_block() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		// the last block of the campaign is still payable
+		System::set_block_number(100);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 100));
+
+		System::set_block_number(101);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 1, None, vec![USER2], 100),
+			Error::<Test>::CampaignExpired
+		);
+		assert_ok!(Task::end_campaign(Origin::signed(BOB), campaign_id));
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 2, None, vec![USER2], 100),
+			Error::<Test>::CampaignExpired
+		);
+	});
+}
+
+#[test]
+fn campaign_status_should_follow_start_and_end_blocks() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			10,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_eq!(Task::campaign_info(campaign_id).unwrap().status, CampaignStatus::Draft);
+
+		// the campaign is reported active from its start block, and stored so by the first
+		// payment
+		System::set_block_number(10);
+		assert_eq!(Task::campaign_info(campaign_id).unwrap().status, CampaignStatus::Active);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().status, CampaignStatus::Draft);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 100));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::CampaignStatusChanged {
+				campaign_id,
+				from: CampaignStatus::Draft,
+				to: CampaignStatus::Active,
+			}
+			.into()));
+		assert_eq!(Task::campaigns(campaign_id).unwrap().status, CampaignStatus::Active);
+
+		// it is reported ended after its end block, and can be settled right away
+		System::set_block_number(101);
+		assert_eq!(Task::campaign_info(campaign_id).unwrap().status, CampaignStatus::Ended);
+		assert_noop!(
+			Task::pause_campaign(Origin::signed(BOB), campaign_id),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), campaign_id));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::CampaignStatusChanged {
+				campaign_id,
+				from: CampaignStatus::Active,
+				to: CampaignStatus::Ended,
+			}
+			.into()));
+		assert_eq!(Task::campaigns(campaign_id).unwrap().status, CampaignStatus::Settled);
+	});
+}
+
+#[test]
+fn can_not_create_campaign_with_invalid_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(50);
+
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(BOB),
+				"0".as_bytes().to_vec(),
+				1000,
+				20,
+				10,
+				RewardAsset::Native,
+				None
+			),
+			Error::<Test>::InvalidCampaignPeriod
+		);
+		// already over
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(BOB),
+				"0".as_bytes().to_vec(),
+				1000,
+				20,
+				40,
+				RewardAsset::Native,
+				None
+			),
+			Error::<Test>::InvalidCampaignPeriod
+		);
+	});
+}
+
+#[test]
+fn payment_should_not_spend_other_campaign_budget() {
+	new_test_ext().execute_with(|| {
+		let campaign_id_0: CampaignId = 0;
+		let campaign_id_1: CampaignId = 1;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id_0,
+			0,
+			None,
+			vec![USER1, USER2],
+			400u32.into()
+		));
+		let campaign = Task::campaigns(campaign_id_0).unwrap();
+		assert_eq!(campaign.spent, 800);
+		assert_eq!(campaign.remaining(), 200);
+
+		// each call is below the campaign value but the budget is already used
+		assert_noop!(
+			Task::payment(
+				Origin::signed(ADMIN),
+				campaign_id_0,
+				1,
+				None,
+				vec![USER3],
+				400u32.into()
+			),
+			Error::<Test>::NotEnoughBalanceForUsers
+		);
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id_0,
+			2,
+			None,
+			vec![USER3],
+			200u32.into()
+		));
+		assert_eq!(Task::campaigns(campaign_id_0).unwrap().remaining(), 0);
+
+		// other campaign is untouched
+		assert_eq!(Task::campaigns(campaign_id_1).unwrap().remaining(), 5000);
+	});
+}
+
+#[test]
+fn client_can_cancel_campaign_before_payment() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_eq!(Balances::free_balance(BOB), 100000 - 5000 - 1000);
+
+		assert_noop!(
+			Task::cancel_campaign(Origin::signed(ALICE), campaign_id),
+			Error::<Test>::PermissionDeny
+		);
+		assert_ok!(Task::cancel_campaign(Origin::signed(BOB), campaign_id));
+		System::assert_last_event(
+			crate::Event::<Test>::CampaignClosed { campaign_id, refunded: 5000, bond: 1000 }.into(),
+		);
+
+		// budget and bond are back
+		assert_eq!(Balances::free_balance(BOB), 100000);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(Task::account_id()), 0);
+
+		// campaign is archived as cancelled
+		let campaign = Task::campaigns(campaign_id).unwrap();
+		assert_eq!(campaign.status, CampaignStatus::Cancelled);
+		assert_eq!(campaign.remaining(), 0);
+		assert_noop!(
+			Task::cancel_campaign(Origin::signed(BOB), campaign_id),
+			Error::<Test>::InvalidStatusTransition
+		);
+	});
+}
+
+#[test]
+fn only_admin_can_cancel_campaign_after_payment() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1, USER2],
+			1000u32.into()
+		));
+
+		assert_noop!(
+			Task::cancel_campaign(Origin::signed(BOB), campaign_id),
+			Error::<Test>::CampaignAlreadyPaid
+		);
+		assert_ok!(Task::cancel_campaign(Origin::signed(ADMIN), campaign_id));
+
+		// only the unused budget goes back to the client
+		assert_eq!(Balances::free_balance(BOB), 100000 - 5000 + 3000);
+		// users can still claim what they have been rewarded
+		assert_eq!(Balances::free_balance(Task::account_id()), 2000);
+	});
+}
+
+#[test]
+fn settle_campaign_should_refund_unused_budget() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::end_campaign(Origin::signed(BOB), campaign_id));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1],
+			1500u32.into()
+		));
+
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_eq!(Balances::free_balance(BOB), 100000 - 5000 + 3500);
+		assert_eq!(Balances::free_balance(Task::account_id()), 1500);
+	});
+}
+
+#[test]
+fn campaign_ids_should_be_assigned_in_order() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let uuid = "6b1e1f0a-3c3e-4c43-9c39-0a2b1f5f5d10".as_bytes().to_vec();
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			uuid.clone(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"other".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		assert_eq!(Task::next_campaign_id(), 2);
+		assert_eq!(Task::campaigns(0).unwrap().client, BOB);
+		assert_eq!(Task::campaigns(1).unwrap().client, ALICE);
+		let campaign_ref: CampaignRefOf<Test> = uuid.clone().try_into().unwrap();
+		assert_eq!(Task::campaign_of_ref(&campaign_ref), Some(0));
+		assert_eq!(Task::campaign_id_of(uuid.clone()), Some(0));
+		assert_eq!(Task::campaign_id_of(b"unknown".to_vec()), None);
+
+		// reference can not be reused, the first campaign is not overwritten
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(ALICE),
+				uuid,
+				5000,
+				0,
+				100,
+				RewardAsset::Native,
+				None
+			),
+			Error::<Test>::CampaignAlreadyExist
+		);
+		assert_eq!(Task::campaigns(0).unwrap().value, 1000);
+
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(ALICE),
+				vec![0u8; 37],
+				5000,
+				0,
+				100,
+				RewardAsset::Native,
+				None
+			),
+			Error::<Test>::CampaignRefTooLong
+		);
+	});
+}
+
+#[test]
+fn user_can_claim_reward_by_themselves() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1],
+			1000u32.into()
+		));
+
+		// claim window is not open yet
+		System::set_block_number(5);
+		assert_noop!(
+			Task::claim_reward(Origin::signed(USER1), campaign_id, 1000),
+			Error::<Test>::InvalidClaim
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Task::claim_reward(Origin::signed(USER1), campaign_id, 1001),
+			Error::<Test>::CanNotClaim
+		);
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), campaign_id, 400));
+		System::assert_last_event(
+			crate::Event::<Test>::Claim {
+				user: USER1,
+				campaign_id,
+				asset: RewardAsset::Native,
+				amount: 400,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(USER1), 400);
+
+		// admin relay still works for the rest
+		assert_ok!(Task::claim(Origin::signed(ADMIN), campaign_id, 600, USER1));
+		assert_eq!(Balances::free_balance(USER1), 1000);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 0);
+
+		// users without reward can not claim
+		assert_noop!(
+			Task::claim_reward(Origin::signed(USER2), campaign_id, 1),
+			Error::<Test>::CanNotClaim
+		);
+	});
+}
+
+#[test]
+fn reward_should_be_paid_automatically_after_payout_duration() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1, USER2, USER3],
+			1000u32.into()
+		));
+
+		// user 2 withdraws part of the reward
+		System::set_block_number(11);
+		assert_ok!(Task::claim_reward(Origin::signed(USER2), campaign_id, 600));
+
+		// nothing due before PayoutDuration
+		Task::on_idle(20, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 0);
+
+		// at most MaxPayoutsPerBlock users are paid in a block
+		System::set_block_number(21);
+		Task::on_idle(21, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 1000);
+		assert_eq!(Balances::free_balance(USER2), 1000);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 0);
+		System::assert_last_event(
+			crate::Event::<Test>::AutoPaid {
+				user: USER2,
+				campaign_id,
+				asset: RewardAsset::Native,
+				amount: 400,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(USER3), 0);
+
+		Task::on_idle(22, u64::MAX);
+		assert_eq!(Balances::free_balance(USER3), 1000);
+		assert_eq!(PayoutQueueHead::<Test>::get(), PayoutQueueTail::<Test>::get());
+	});
+}
+
+#[test]
+fn new_reward_should_delay_automatic_payout() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 1000, 1, 50));
+		assert_ok!(Task::add_task(
+			Origin::signed(BOB),
+			campaign_id,
+			TaskKind::SocialFollow,
+			500,
+			1,
+			50
+		));
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			Some(0),
+			vec![USER1],
+			1000u32.into()
+		));
+		System::set_block_number(10);
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			1,
+			Some(1),
+			vec![USER1],
+			500u32.into()
+		));
+
+		// first entry is superseded by the second reward
+		Task::on_idle(21, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 0);
+
+		Task::on_idle(30, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 1500);
+	});
+}
+
+#[test]
+fn payment_should_fail_when_payout_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		let users: Vec<AccountId> = (10..21).map(|i| AccountId::new([i; 32])).collect();
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, users, 10u32.into()),
+			Error::<Test>::PayoutQueueFull
+		);
+		assert_noop!(
+			Task::payment(
+				Origin::signed(ADMIN),
+				campaign_id,
+				1,
+				None,
+				vec![USER1; 17],
+				10u32.into()
+			),
+			Error::<Test>::TooManyUsers
+		);
+
+		// the limit is per campaign, a full campaign does not block the others
+		let users: Vec<AccountId> = (10..20).map(|i| AccountId::new([i; 32])).collect();
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 2, None, users, 10u32.into()));
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 3, None, vec![USER1], 10u32.into()),
+			Error::<Test>::PayoutQueueFull
+		);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 4, None, vec![USER1], 10u32.into()));
+
+		// paid entries free their slots
+		Task::on_idle(20, u64::MAX);
+		assert_eq!(PendingPayouts::<Test>::get(campaign_id), 8);
+	});
+}
+
+#[test]
+fn failed_automatic_payout_should_be_retried() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 1000));
+
+		// the pallet account can not pay, the entry is pushed back instead of being dropped
+		Balances::make_free_balance_be(&Task::account_id(), 0);
+		Task::on_idle(21, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 0);
+		assert_eq!(PayoutQueueHead::<Test>::get(), 1);
+		assert_eq!(PayoutQueue::<Test>::get(1), Some((USER1, campaign_id, 41, 1)));
+		assert_eq!(PendingPayouts::<Test>::get(campaign_id), 1);
+
+		Balances::make_free_balance_be(&Task::account_id(), 5000);
+		Task::on_idle(30, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 0);
+		Task::on_idle(41, u64::MAX);
+		assert_eq!(Balances::free_balance(USER1), 1000);
+		assert_eq!(PendingPayouts::<Test>::get(campaign_id), 0);
+	});
+}
+
+#[test]
+fn automatic_payout_should_be_abandoned_after_max_attempts() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 1000));
+
+		// every payout fails, the entry is retried `MAX_PAYOUT_ATTEMPTS` times in total
+		Balances::make_free_balance_be(&Task::account_id(), 0);
+		Task::on_idle(21, u64::MAX);
+		Task::on_idle(41, u64::MAX);
+		assert_eq!(PayoutQueue::<Test>::get(2), Some((USER1, campaign_id, 61, 2)));
+		Task::on_idle(61, u64::MAX);
+		System::assert_last_event(Event::AutoPayoutAbandoned { user: USER1, campaign_id }.into());
+		assert_eq!(PayoutQueue::<Test>::iter().count(), 0);
+		assert_eq!(PendingPayouts::<Test>::get(campaign_id), 0);
+
+		// the reward is left to be claimed
+		Balances::make_free_balance_be(&Task::account_id(), 5000);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 1000);
+		System::set_block_number(61);
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), campaign_id, 1000));
+		assert_eq!(Balances::free_balance(USER1), 1000);
+	});
+}
+
+#[test]
+fn campaign_can_be_funded_and_paid_in_asset() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let usdn = RewardAsset::Asset(7);
+		System::set_block_number(1);
+		assert_ok!(Assets::force_create(Origin::root(), 7, ALICE, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 7, BOB, 10000));
+
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			usdn,
+			None
+		));
+		// budget is taken in the asset, bond in native currency, the first campaign in the asset
+		// also pays its minimum balance
+		assert_eq!(Assets::balance(7, BOB), 4999);
+		assert_eq!(Assets::balance(7, Task::account_id()), 5001);
+		assert_eq!(Balances::reserved_balance(BOB), 1000);
+		assert_eq!(Balances::free_balance(BOB), 99000);
+
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1, USER2],
+			1000u32.into()
+		));
+		assert_eq!(Task::claimable(&USER1, usdn).0, 1000);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 0);
+
+		System::set_block_number(11);
+		// nothing to claim from another campaign
+		assert_noop!(
+			Task::claim_reward(Origin::signed(USER1), campaign_id + 1, 1000),
+			Error::<Test>::CanNotClaim
+		);
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), campaign_id, 1000));
+		assert_eq!(Assets::balance(7, USER1), 1000);
+
+		// unused budget is refunded in the asset
+		assert_ok!(Task::cancel_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_eq!(Assets::balance(7, BOB), 7999);
+		assert_eq!(Assets::balance(7, Task::account_id()), 1001);
+
+		// remaining reward is paid automatically in the asset
+		Task::on_idle(21, u64::MAX);
+		assert_eq!(Assets::balance(7, USER2), 1000);
+		assert_eq!(Assets::balance(7, Task::account_id()), 1);
+	});
+}
+
+#[test]
+fn asset_payouts_should_keep_the_minimum_balance() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let asset = RewardAsset::Asset(8);
+		System::set_block_number(1);
+		assert_ok!(Assets::force_create(Origin::root(), 8, ALICE, true, 5));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 8, BOB, 100));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 8, USER1, 5));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 8, USER2, 5));
+
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			6,
+			0,
+			100,
+			asset,
+			None
+		));
+		assert_eq!(Assets::balance(8, Task::account_id()), 6 + 5);
+		assert_eq!(Assets::balance(8, BOB), 100 - 6 - 5);
+
+		// each user gets exactly its reward, nothing is swept as dust
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1, USER2],
+			3
+		));
+		System::set_block_number(11);
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), campaign_id, 3));
+		assert_eq!(Assets::balance(8, USER1), 8);
+		assert_ok!(Task::claim_reward(Origin::signed(USER2), campaign_id, 3));
+		assert_eq!(Assets::balance(8, USER2), 8);
+		assert_eq!(Assets::balance(8, Task::account_id()), 5);
+
+		// later campaigns in the asset do not pay the minimum balance again
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"1".as_bytes().to_vec(),
+			6,
+			0,
+			100,
+			asset,
+			None
+		));
+		assert_eq!(Assets::balance(8, BOB), 100 - 6 - 5 - 6);
+	});
+}
+
+#[test]
+fn rewarded_users_should_get_campaign_badge() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let class = BadgeClassStart::get() + campaign_id;
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(BOB),
+				"0".as_bytes().to_vec(),
+				1000,
+				0,
+				100,
+				RewardAsset::Native,
+				Some(0)
+			),
+			Error::<Test>::InvalidBadgeSupply
+		);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			Some(2)
+		));
+		// the pallet owns the class, its deposit is paid by the client
+		assert_eq!(<Uniques as Inspect<AccountId>>::class_owner(&class), Some(Task::account_id()));
+		assert_eq!(Balances::reserved_balance(Task::account_id()), 2);
+		assert_eq!(Balances::free_balance(BOB), 100000 - 1000 - 1000 - 2);
+		assert_noop!(
+			Uniques::mint(Origin::signed(BOB), class, 7, BOB),
+			pallet_uniques::Error::<Test>::NoPermission
+		);
+
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			0,
+			None,
+			vec![USER1, USER2],
+			500
+		));
+		assert_eq!(<Uniques as Inspect<AccountId>>::owner(&class, &0), Some(USER1));
+		assert_eq!(<Uniques as Inspect<AccountId>>::owner(&class, &1), Some(USER2));
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 500);
+		assert_eq!(Balances::reserved_balance(Task::account_id()), 2 + 2);
+		assert_eq!(Balances::free_balance(BOB), 100000 - 1000 - 1000 - 2 - 2);
+
+		// supply cap is reached
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 1, None, vec![USER3], 0),
+			Error::<Test>::BadgeSupplyExceeded
+		);
+	});
+}
+
+#[test]
+fn taken_badge_classes_should_be_skipped() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let class = BadgeClassStart::get();
+		assert_ok!(Uniques::create(Origin::signed(ALICE), class, ALICE));
+
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Native,
+			Some(2)
+		));
+		assert_eq!(Task::campaigns(0).unwrap().badge.unwrap().class, class + 1);
+		assert_eq!(<Uniques as Inspect<AccountId>>::class_owner(&class), Some(ALICE));
+
+		// every id that could be tried is taken
+		for i in 2..2 + MAX_BADGE_CLASS_ATTEMPTS {
+			assert_ok!(Uniques::create(Origin::signed(ALICE), class + i, ALICE));
+		}
+		assert_noop!(
+			Task::create_campaign(
+				Origin::signed(BOB),
+				"1".as_bytes().to_vec(),
+				1000,
+				0,
+				100,
+				RewardAsset::Native,
+				Some(2)
+			),
+			Error::<Test>::BadgeClassUnavailable
+		);
+	});
+}
+
+#[test]
+fn badge_only_campaign_should_not_queue_payouts() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let class = BadgeClassStart::get() + campaign_id;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			0,
+			0,
+			100,
+			RewardAsset::Native,
+			Some(10)
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 0));
+		assert_eq!(<Uniques as Inspect<AccountId>>::owner(&class, &0), Some(USER1));
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 0);
+		assert_eq!(PayoutQueueTail::<Test>::get(), 0);
+	});
+}
+
+fn hash_pair(a: sp_core::H256, b: sp_core::H256) -> sp_core::H256 {
+	use sp_runtime::traits::BlakeTwo256;
+	if a <= b {
+		BlakeTwo256::hash_of(&(a, b))
+	} else {
+		BlakeTwo256::hash_of(&(b, a))
+	}
+}
+
+#[test]
+fn users_can_claim_merkle_distribution() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		let l0 = Task::distribution_leaf(0, &USER1, 300);
+		let l1 = Task::distribution_leaf(1, &USER2, 200);
+		let l2 = Task::distribution_leaf(2, &USER3, 500);
+		let root = hash_pair(hash_pair(l0, l1), l2);
+
+		assert_noop!(
+			Task::publish_distribution(Origin::signed(USER1), campaign_id, root, 1000, 3),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::publish_distribution(Origin::signed(ADMIN), campaign_id, root, 5001, 3),
+			Error::<Test>::NotEnoughBalanceForUsers
+		);
+		assert_ok!(Task::publish_distribution(Origin::signed(ADMIN), campaign_id, root, 1000, 3));
+		assert_noop!(
+			Task::publish_distribution(Origin::signed(ADMIN), campaign_id, root, 1000, 3),
+			Error::<Test>::DistributionAlreadyExist
+		);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().remaining(), 4000);
+
+		assert_ok!(Task::claim_distribution(
+			Origin::signed(USER1),
+			campaign_id,
+			0,
+			300,
+			vec![l1, l2]
+		));
+		System::assert_last_event(
+			crate::Event::<Test>::DistributionClaimed {
+				campaign_id,
+				index: 0,
+				user: USER1,
+				amount: 300,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(USER1), 300);
+		assert_noop!(
+			Task::claim_distribution(Origin::signed(USER1), campaign_id, 0, 300, vec![l1, l2]),
+			Error::<Test>::AlreadyClaimed
+		);
+
+		// amount, account and index are all part of the leaf
+		assert_noop!(
+			Task::claim_distribution(Origin::signed(USER2), campaign_id, 1, 300, vec![l0, l2]),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			Task::claim_distribution(Origin::signed(USER3), campaign_id, 1, 200, vec![l0, l2]),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			Task::claim_distribution(Origin::signed(USER3), campaign_id, 3, 500, vec![]),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			Task::claim_distribution(Origin::signed(USER3), campaign_id, 2, 500, vec![l0; 9]),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(Task::claim_distribution(
+			Origin::signed(USER3),
+			campaign_id,
+			2,
+			500,
+			vec![hash_pair(l0, l1)]
+		));
+		assert_eq!(Balances::free_balance(USER3), 500);
+	});
+}
+
+#[test]
+fn migration_to_v1_should_convert_old_storage() {
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		// campaign keyed by its reference and single native balance of the initial layout
+		let campaign_ref = b"abc".to_vec();
+		put_storage_value(
+			b"Task",
+			b"Campaigns",
+			&Twox64Concat::hash(&campaign_ref.encode()),
+			(BOB, 5000u128, 1000u128),
+		);
+		// this one was paid, its bond was already unreserved
+		put_storage_value(
+			b"Task",
+			b"Campaigns",
+			&Twox64Concat::hash(&b"def".to_vec().encode()),
+			(BOB, 5000u128, 1000u128),
+		);
+		put_storage_value(
+			b"Task",
+			b"BalanceUser",
+			&Twox64Concat::hash(&USER1.encode()),
+			(3u64, 700u128),
+		);
+		assert_ok!(Balances::reserve(&BOB, 1000));
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Task::on_chain_storage_version(), StorageVersion::new(1));
+
+		let campaign_id: CampaignId = 0;
+		let campaign_ref: CampaignRefOf<Test> = campaign_ref.try_into().unwrap();
+		assert!(Task::campaign_of_ref(campaign_ref).is_some());
+		assert_eq!(Task::next_campaign_id(), 2);
+		let campaign = Task::campaigns(campaign_id).unwrap();
+		assert_eq!(campaign.status, CampaignStatus::Ended);
+		assert_eq!(campaign.remaining(), 0);
+		// only what BOB still has reserved is bonded
+		assert_eq!(campaign.bond + Task::campaigns(1).unwrap().bond, 1000);
+
+		// left in the initial layout, v3 moves it to the rewards
+		let balance_key = Twox64Concat::hash(&USER1.encode());
+		assert_eq!(
+			get_storage_value::<(u64, u128)>(b"Task", b"BalanceUser", &balance_key),
+			Some((3, 700))
+		);
+		assert_eq!(PayoutQueueTail::<Test>::get(), 0);
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(get_storage_value::<(u64, u128)>(b"Task", b"BalanceUser", &balance_key), None);
+		assert_eq!(
+			Task::rewards(USER1, crate::migrations::v3::LEGACY_CAMPAIGN)
+				.map(|reward| reward.amount),
+			Some(700)
+		);
+
+		// settling the old campaigns releases the bond, and nothing else
+		assert_ok!(Balances::reserve(&BOB, 300));
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), 0));
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), 1));
+		assert_eq!(Balances::reserved_balance(BOB), 300);
+	});
+}
+
+#[test]
+fn privileged_calls_should_be_gated_by_origins() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		assert_noop!(Task::add_admin(Origin::signed(USER1), USER3), DispatchError::BadOrigin);
+		assert_ok!(Task::add_admin(Origin::root(), USER3));
+		assert_ok!(Task::payment(Origin::signed(USER3), campaign_id, 0, None, vec![USER1], 100));
+		// root does not need to be an admin
+		assert_ok!(Task::payment(Origin::root(), campaign_id, 1, None, vec![USER2], 100));
+
+		assert_ok!(Task::remove_admin(Origin::root(), USER3));
+		assert_noop!(
+			Task::payment(Origin::signed(USER3), campaign_id, 2, None, vec![USER1], 100),
+			Error::<Test>::PermissionDeny
+		);
+	});
+}
+
+#[test]
+fn campaign_roles_should_be_enforced() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		// only an owner manages roles
+		assert_noop!(
+			Task::grant_role(Origin::signed(USER1), campaign_id, USER1, CampaignRole::Owner),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::grant_role(Origin::signed(BOB), campaign_id, BOB, CampaignRole::Auditor),
+			Error::<Test>::ClientRoleFixed
+		);
+		assert_ok!(Task::grant_role(
+			Origin::signed(BOB),
+			campaign_id,
+			USER1,
+			CampaignRole::Operator
+		));
+		assert_ok!(Task::grant_role(
+			Origin::signed(ADMIN),
+			campaign_id,
+			USER2,
+			CampaignRole::Auditor
+		));
+		System::assert_last_event(
+			Event::RoleGranted { campaign_id, who: USER2, role: CampaignRole::Auditor }.into(),
+		);
+		assert_eq!(
+			Task::role_of(campaign_id, &Task::campaigns(campaign_id).unwrap(), &BOB),
+			Some(CampaignRole::Owner)
+		);
+
+		// operators reward users but can not manage the campaign, auditors can do neither
+		assert_ok!(Task::payment(Origin::signed(USER1), campaign_id, 0, None, vec![USER3], 100));
+		assert_noop!(
+			Task::pause_campaign(Origin::signed(USER1), campaign_id),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::grant_role(Origin::signed(USER1), campaign_id, USER3, CampaignRole::Operator),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::payment(Origin::signed(USER2), campaign_id, 1, None, vec![USER3], 100),
+			Error::<Test>::PermissionDeny
+		);
+
+		// roles are per campaign
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_noop!(
+			Task::payment(Origin::signed(USER1), 1, 2, None, vec![USER3], 100),
+			Error::<Test>::PermissionDeny
+		);
+
+		// another owner can manage the campaign
+		assert_ok!(Task::grant_role(Origin::signed(BOB), campaign_id, USER2, CampaignRole::Owner));
+		assert_ok!(Task::pause_campaign(Origin::signed(USER2), campaign_id));
+
+		assert_ok!(Task::revoke_role(Origin::signed(USER2), campaign_id, USER1));
+		System::assert_last_event(
+			Event::RoleRevoked { campaign_id, who: USER1, role: CampaignRole::Operator }.into(),
+		);
+		assert_noop!(
+			Task::revoke_role(Origin::signed(BOB), campaign_id, USER1),
+			Error::<Test>::RoleNotExist
+		);
+		assert_ok!(Task::resume_campaign(Origin::signed(BOB), campaign_id));
+		assert_noop!(
+			Task::payment(Origin::signed(USER1), campaign_id, 3, None, vec![USER3], 100),
+			Error::<Test>::PermissionDeny
+		);
+	});
+}
+
+#[test]
+fn last_admins_can_not_be_removed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Task::add_admin(Origin::root(), ADMIN), Error::<Test>::AlreadyAdmin);
+		assert_noop!(Task::remove_admin(Origin::root(), USER1), Error::<Test>::NotAdmin);
+		assert_noop!(Task::remove_admin(Origin::root(), ADMIN), Error::<Test>::TooFewAdmins);
+
+		assert_ok!(Task::add_admin(Origin::signed(ADMIN), USER1));
+		assert_eq!(Task::admin_count(), 2);
+		assert_noop!(
+			Task::remove_admin(Origin::signed(ADMIN), ADMIN),
+			Error::<Test>::CannotRemoveSelf
+		);
+		assert_ok!(Task::remove_admin(Origin::signed(USER1), ADMIN));
+		assert_eq!(Task::admin_count(), 1);
+		assert_noop!(Task::remove_admin(Origin::root(), USER1), Error::<Test>::TooFewAdmins);
+
+		for admin in
+			[USER2, USER3, ALICE, BOB, ADMIN, AccountId::new([6u8; 32]), AccountId::new([7u8; 32])]
+		{
+			assert_ok!(Task::add_admin(Origin::root(), admin));
+		}
+		assert_noop!(
+			Task::add_admin(Origin::root(), AccountId::new([8u8; 32])),
+			Error::<Test>::TooManyAdmins
+		);
+	});
+}
+
+#[test]
+fn admin_seat_can_be_handed_over() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Task::propose_admin_handover(Origin::signed(USER1), USER2),
+			Error::<Test>::NotAdmin
+		);
+		assert_noop!(
+			Task::propose_admin_handover(Origin::signed(ADMIN), ADMIN),
+			Error::<Test>::AlreadyAdmin
+		);
+		assert_ok!(Task::propose_admin_handover(Origin::signed(ADMIN), USER1));
+		assert_eq!(Task::admin_handovers(ADMIN), Some(USER1));
+
+		assert_noop!(
+			Task::accept_admin_handover(Origin::signed(USER2), ADMIN),
+			Error::<Test>::HandoverNotExist
+		);
+		assert_ok!(Task::accept_admin_handover(Origin::signed(USER1), ADMIN));
+		System::assert_last_event(Event::AdminHandedOver { from: ADMIN, to: USER1 }.into());
+		assert!(Task::only_admin(USER1));
+		assert!(!Task::only_admin(ADMIN));
+		assert_eq!(Task::admin_count(), 1);
+		assert_eq!(Task::admin_handovers(ADMIN), None);
+	});
+}
+
+#[test]
+fn root_can_force_set_admins() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Task::propose_admin_handover(Origin::signed(ADMIN), USER3));
+		assert_noop!(
+			Task::force_set_admins(Origin::signed(ADMIN), vec![USER1]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(Task::force_set_admins(Origin::root(), vec![]), Error::<Test>::TooFewAdmins);
+
+		assert_ok!(Task::force_set_admins(Origin::root(), vec![USER1, USER2, USER1]));
+		assert_eq!(Task::admin_count(), 2);
+		assert!(Task::only_admin(USER1) && Task::only_admin(USER2));
+		assert!(!Task::only_admin(ADMIN));
+		assert_eq!(Task::admin_handovers(ADMIN), None);
+	});
+}
+
+#[test]
+fn migration_to_v2_should_count_admins() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Task>();
+		Admins::<Test>::insert(USER1, true);
+		AdminCount::<Test>::kill();
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(Task::on_chain_storage_version(), StorageVersion::new(2));
+		assert_eq!(Task::admin_count(), 2);
+	});
+}
+
+#[test]
+fn bond_should_be_held_until_settlement() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 1000));
+		assert_eq!(Balances::reserved_balance(BOB), 1000);
+
+		assert_ok!(Task::end_campaign(Origin::signed(BOB), campaign_id));
+		assert_ok!(Task::settle_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::BondReleased { campaign_id, client: BOB, amount: 1000 }.into()));
+		assert_noop!(
+			Task::slash_bond(Origin::signed(ADMIN), campaign_id, 1, None),
+			Error::<Test>::InsufficientBond
+		);
+	});
+}
+
+#[test]
+fn bond_of_fraudulent_campaign_can_be_slashed() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		let issuance = Balances::total_issuance();
+
+		assert_noop!(
+			Task::slash_bond(Origin::signed(BOB), campaign_id, 100, None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Task::slash_bond(Origin::signed(ADMIN), campaign_id, 1001, None),
+			Error::<Test>::InsufficientBond
+		);
+
+		// part of the bond goes to an affected user
+		assert_ok!(Task::slash_bond(Origin::signed(ADMIN), campaign_id, 300, Some(USER1)));
+		System::assert_last_event(
+			Event::BondSlashed { campaign_id, client: BOB, amount: 300, beneficiary: Some(USER1) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(USER1), 300);
+
+		// the rest goes to `Slash`, burnt in the mock
+		assert_ok!(Task::slash_bond(Origin::signed(ADMIN), campaign_id, 700, None));
+		assert_eq!(Balances::total_issuance(), issuance - 700);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().bond, 0);
+
+		// the unused budget is still refunded
+		assert_ok!(Task::cancel_campaign(Origin::signed(ADMIN), campaign_id));
+		assert_eq!(Balances::free_balance(BOB), 100000 - 5000 - 1000 + 5000);
+	});
+}
+
+#[test]
+fn users_should_be_rewarded_for_campaign_tasks() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+
+		assert_noop!(
+			Task::add_task(Origin::signed(USER1), campaign_id, TaskKind::Quiz, 100, 2, 10),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 0, 10),
+			Error::<Test>::InvalidTask
+		);
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 2, 10));
+		System::assert_last_event(
+			Event::TaskAdded {
+				campaign_id,
+				task_id: 0,
+				kind: TaskKind::Quiz,
+				reward: 100,
+				max_completions: 2,
+				deadline: 10,
+			}
+			.into(),
+		);
+		assert_ok!(Task::add_task(
+			Origin::signed(ADMIN),
+			campaign_id,
+			TaskKind::SocialFollow,
+			50,
+			10,
+			50
+		));
+		assert_noop!(
+			Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::OnChainAction, 10, 1, 10),
+			Error::<Test>::TooManyTasks
+		);
+
+		// users are rewarded for a task, with its reward
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 100),
+			Error::<Test>::TaskRequired
+		);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 1, Some(2), vec![USER1], 100),
+			Error::<Test>::TaskNotExist
+		);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 2, Some(0), vec![USER1], 150),
+			Error::<Test>::RewardMismatch
+		);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 3, Some(0), vec![USER1], 100));
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 100);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 4, Some(0), vec![USER2, USER3], 100),
+			Error::<Test>::TaskCompletionsExceeded
+		);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 5, Some(0), vec![USER2], 100));
+		assert_eq!(Task::tasks(campaign_id, 0).unwrap().completions, 2);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Task::payment(Origin::signed(ADMIN), campaign_id, 6, Some(0), vec![USER3], 100),
+			Error::<Test>::TaskExpired
+		);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 7, Some(1), vec![USER3], 50));
+		System::assert_last_event(
+			Event::Payment {
+				campaign_id,
+				task_id: Some(1),
+				account: vec![USER3].try_into().unwrap(),
+			}
+			.into(),
+		);
+
+		// no task can be added once the campaign has ended
+		assert_ok!(Task::end_campaign(Origin::signed(BOB), campaign_id));
+		assert_noop!(
+			Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 2, 50),
+			Error::<Test>::CampaignNotEditable
+		);
+	});
+}
+
+#[test]
+fn approved_submissions_should_be_rewarded() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let (proof, other_proof) = (sp_core::H256::repeat_byte(1), sp_core::H256::repeat_byte(2));
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_noop!(
+			Task::submit_proof(Origin::signed(USER1), campaign_id, 0, proof),
+			Error::<Test>::TaskNotExist
+		);
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 5, 10));
+		assert_ok!(Task::grant_role(
+			Origin::signed(BOB),
+			campaign_id,
+			USER3,
+			CampaignRole::Operator
+		));
+
+		assert_ok!(Task::submit_proof(Origin::signed(USER1), campaign_id, 0, proof));
+		System::assert_last_event(
+			Event::ProofSubmitted { campaign_id, task_id: 0, user: USER1, proof }.into(),
+		);
+		assert_noop!(
+			Task::submit_proof(Origin::signed(USER1), campaign_id, 0, other_proof),
+			Error::<Test>::SubmissionAlreadyExist
+		);
+		assert_ok!(Task::submit_proof(Origin::signed(USER2), campaign_id, 0, proof));
+		assert!(PendingSubmissions::<Test>::get((campaign_id, 0), USER1));
+
+		// only operators review submissions
+		assert_noop!(
+			Task::approve_submission(Origin::signed(USER2), campaign_id, 0, USER1),
+			Error::<Test>::PermissionDeny
+		);
+		assert_noop!(
+			Task::approve_submission(Origin::signed(USER3), campaign_id, 0, ALICE),
+			Error::<Test>::SubmissionNotExist
+		);
+		assert_ok!(Task::approve_submission(Origin::signed(USER3), campaign_id, 0, USER1));
+		System::assert_last_event(
+			Event::SubmissionApproved { campaign_id, task_id: 0, user: USER1, proof, amount: 100 }
+				.into(),
+		);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 100);
+		assert_eq!(Task::tasks(campaign_id, 0).unwrap().completions, 1);
+		// decided submissions leave the index verified by the offchain worker
+		assert!(!PendingSubmissions::<Test>::contains_key((campaign_id, 0), USER1));
+		assert_noop!(
+			Task::approve_submission(Origin::signed(USER3), campaign_id, 0, USER1),
+			Error::<Test>::SubmissionNotPending
+		);
+
+		// a rejected user is not rewarded and can submit again
+		assert_ok!(Task::reject_submission(Origin::signed(ADMIN), campaign_id, 0, USER2));
+		System::assert_last_event(
+			Event::SubmissionRejected { campaign_id, task_id: 0, user: USER2, proof }.into(),
+		);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 0);
+		assert!(!PendingSubmissions::<Test>::contains_key((campaign_id, 0), USER2));
+		assert_ok!(Task::submit_proof(Origin::signed(USER2), campaign_id, 0, other_proof));
+		assert!(PendingSubmissions::<Test>::get((campaign_id, 0), USER2));
+		assert_eq!(
+			Task::submissions((campaign_id, 0), USER2).unwrap().status,
+			SubmissionStatus::Pending
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Task::submit_proof(Origin::signed(USER3), campaign_id, 0, proof),
+			Error::<Test>::TaskExpired
+		);
+		assert_noop!(
+			Task::approve_submission(Origin::signed(USER3), campaign_id, 0, USER2),
+			Error::<Test>::TaskExpired
+		);
+	});
+}
+
+#[test]
+fn paid_users_should_have_their_submission_approved() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let proof = sp_core::H256::repeat_byte(1);
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 5, 10));
+		assert_ok!(Task::submit_proof(Origin::signed(USER1), campaign_id, 0, proof));
+
+		// the payment decides the pending submission, the offchain worker has nothing to send
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, Some(0), vec![USER1], 100));
+		assert!(!PendingSubmissions::<Test>::contains_key((campaign_id, 0), USER1));
+		assert_eq!(
+			Task::submissions((campaign_id, 0), USER1).unwrap().status,
+			SubmissionStatus::Approved
+		);
+		assert_noop!(
+			Task::approve_submission(Origin::signed(ADMIN), campaign_id, 0, USER1),
+			Error::<Test>::SubmissionNotPending
+		);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 100);
+
+		// users already paid for the task can not submit
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 1, Some(0), vec![USER2], 100));
+		assert_noop!(
+			Task::submit_proof(Origin::signed(USER2), campaign_id, 0, proof),
+			Error::<Test>::AlreadyRewarded
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_should_send_verdicts_of_pending_submissions() {
+	use codec::Decode;
+	use sp_core::offchain::{
+		testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	};
+	use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+	use sp_runtime::offchain::StorageKind;
+	use std::sync::Arc;
+
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	SyncCryptoStore::sr25519_generate_new(&keystore, crate::KEY_TYPE, None).unwrap();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	t.execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		let proof = sp_core::H256::repeat_byte(1);
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 5, 10));
+		assert_ok!(Task::submit_proof(Origin::signed(USER1), campaign_id, 0, proof));
+
+		// nothing is verified until the endpoint is set
+		Task::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			crate::offchain::ENDPOINT_KEY,
+			b"http://localhost:8000/verify",
+		);
+		state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: format!(
+				"http://localhost:8000/verify?campaign_id=0&task_id=0&user=0x{}&proof=0x{}",
+				sp_core::hexdisplay::HexDisplay::from(&USER1.encode()),
+				sp_core::hexdisplay::HexDisplay::from(&proof.encode()),
+			),
+			response: Some(b"true\n".to_vec()),
+			sent: true,
+			..Default::default()
+		});
+		Task::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert!(tx.signature.is_some());
+		assert_eq!(
+			tx.call,
+			Call::Task(crate::Call::approve_submission { campaign_id, task_id: 0, user: USER1 })
+		);
+
+		// the verdict is not sent again while it may still be included
+		Task::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn vesting_rewards_should_be_released_over_time() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		let schedule = VestingSchedule { cliff: 10, duration: 100 };
+		assert_noop!(
+			Task::set_vesting(Origin::signed(USER1), campaign_id, Some(schedule)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Task::set_vesting(Origin::signed(BOB), campaign_id, None));
+		System::assert_last_event(Event::VestingRemoved { campaign_id }.into());
+		assert_ok!(Task::set_vesting(Origin::signed(BOB), campaign_id, Some(schedule)));
+		System::assert_last_event(Event::VestingSet { campaign_id, schedule }.into());
+
+		// distribution claims would not follow the schedule
+		assert_noop!(
+			Task::publish_distribution(
+				Origin::signed(ADMIN),
+				campaign_id,
+				sp_core::H256::zero(),
+				1000,
+				1
+			),
+			Error::<Test>::DistributionWithVesting
+		);
+
+		// vesting rewards are not queued for payout
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 1000));
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native), (0, 0));
+		assert_eq!(PayoutQueueTail::<Test>::get(), 0);
+		assert_noop!(
+			Task::set_vesting(Origin::signed(BOB), campaign_id, None),
+			Error::<Test>::VestingAlreadyStarted
+		);
+
+		// nothing is released before the cliff
+		System::set_block_number(11);
+		assert_noop!(
+			Task::claim_vested(Origin::signed(USER1), RewardAsset::Native),
+			Error::<Test>::NothingVested
+		);
+
+		// half of the reward is released halfway through the duration
+		System::set_block_number(61);
+		assert_eq!(Task::vesting_balance(&USER1, RewardAsset::Native), (500, 500));
+		assert_ok!(Task::claim_vested(Origin::signed(USER1), RewardAsset::Native));
+		System::assert_last_event(
+			Event::VestedClaimed { user: USER1, asset: RewardAsset::Native, amount: 500 }.into(),
+		);
+		assert_eq!(Balances::free_balance(USER1), 500);
+		assert_eq!(Task::vesting_balance(&USER1, RewardAsset::Native), (0, 500));
+
+		// the whole reward is released once the duration has passed
+		System::set_block_number(200);
+		assert_ok!(Task::claim_vested(Origin::signed(USER1), RewardAsset::Native));
+		assert_eq!(Balances::free_balance(USER1), 1000);
+		assert!(!VestingPositions::<Test>::contains_key(USER1, RewardAsset::Native));
+
+		// a user vests a limited number of rewards at the same time, the next ones are added to
+		// the latest
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			300,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::set_vesting(Origin::signed(ALICE), 1, Some(schedule)));
+		assert_ok!(Task::add_task(Origin::signed(ALICE), 1, TaskKind::Quiz, 100, 1, 300));
+		assert_ok!(Task::add_task(Origin::signed(ALICE), 1, TaskKind::SocialFollow, 100, 1, 300));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 1, Some(0), vec![USER2], 100));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 2, Some(1), vec![USER2], 100));
+		assert_ok!(Task::add_task(Origin::signed(ALICE), 1, TaskKind::Quiz, 100, 2, 300));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 3, Some(2), vec![USER2, USER3], 100));
+		let positions = VestingPositions::<Test>::get(USER2, RewardAsset::Native);
+		assert_eq!(positions.len(), 2);
+		assert_eq!(positions[0].total, 100);
+		assert_eq!(positions[1].total, 200);
+		assert_eq!(VestingPositions::<Test>::get(USER3, RewardAsset::Native)[0].total, 100);
+		assert_eq!(Task::vesting_balance(&USER2, RewardAsset::Native), (0, 300));
+	});
+}
+
+#[test]
+fn rewards_should_be_tracked_per_campaign() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 0, 0, None, vec![USER1], 1000));
+		System::set_block_number(8);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 1, None, vec![USER1], 300));
+
+		// a new reward from campaign 1 does not delay the claim of campaign 0
+		System::set_block_number(11);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native), (1300, 18));
+		assert_noop!(
+			Task::claim_reward(Origin::signed(USER1), 1, 300),
+			Error::<Test>::InvalidClaim
+		);
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), 0, 400));
+		let reward = Task::rewards(USER1, 0).unwrap();
+		assert_eq!((reward.amount, reward.claimed, reward.credited_at), (1000, 400, 1));
+
+		// claim_all only pays the campaigns whose claim window is open
+		assert_ok!(Task::claim_all(Origin::signed(USER1), 10));
+		System::assert_last_event(
+			Event::Claim { user: USER1, campaign_id: 0, asset: RewardAsset::Native, amount: 600 }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(USER1), 1000);
+		assert_eq!(Task::rewards(USER1, 0), None);
+		assert_noop!(Task::claim_all(Origin::signed(USER1), 10), Error::<Test>::NothingToClaim);
+
+		System::set_block_number(18);
+		assert_ok!(Task::claim_all(Origin::signed(USER1), 10));
+		assert_eq!(Balances::free_balance(USER1), 1300);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native), (0, 0));
+	});
+}
+
+#[test]
+fn migration_to_v3_should_move_user_balances_to_rewards() {
+	use crate::migrations::v3::{balance_key, LEGACY_CAMPAIGN};
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Task>();
+		put_storage_value(
+			b"Task",
+			b"BalanceUser",
+			&balance_key::<Test>(&USER1, &RewardAsset::Native),
+			(3u64, 700u128),
+		);
+		put_storage_value(
+			b"Task",
+			b"BalanceUser",
+			&balance_key::<Test>(&USER1, &RewardAsset::Asset(7)),
+			(5u64, 50u128),
+		);
+		put_storage_value(
+			b"Task",
+			b"PayoutQueue",
+			&Twox64Concat::hash(&0u32.encode()),
+			(USER1, RewardAsset::<u32>::Native, 23u64),
+		);
+		PayoutQueueTail::<Test>::put(1);
+		Balances::make_free_balance_be(&Task::account_id(), 1000);
+
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(Task::on_chain_storage_version(), StorageVersion::new(3));
+		assert!(!LegacyBalancesLeft::<Test>::get());
+
+		// nothing is paid out, each balance is kept as a reward
+		assert_eq!(Balances::free_balance(USER1), 0);
+		assert_eq!(
+			get_storage_value::<(u64, u128)>(
+				b"Task",
+				b"BalanceUser",
+				&balance_key::<Test>(&USER1, &RewardAsset::Native)
+			),
+			None
+		);
+		let rewards: Vec<_> = Rewards::<Test>::iter_prefix(USER1)
+			.map(|(campaign_id, reward)| (campaign_id, reward.asset, reward.unclaimed()))
+			.collect();
+		assert_eq!(rewards.len(), 2);
+		assert!(rewards.iter().all(|(campaign_id, _, _)| *campaign_id >= LEGACY_CAMPAIGN - 1));
+		assert!(rewards
+			.iter()
+			.any(|(_, asset, amount)| *asset == RewardAsset::Native && *amount == 700));
+		assert!(rewards
+			.iter()
+			.any(|(_, asset, amount)| *asset == RewardAsset::Asset(7) && *amount == 50));
+		assert_eq!(PayoutQueueTail::<Test>::get(), 0);
+		assert_eq!(PayoutQueue::<Test>::iter().count(), 0);
+
+		// users claim moved balances as any other reward
+		let campaign_id = rewards
+			.iter()
+			.find(|(_, asset, _)| *asset == RewardAsset::Native)
+			.map(|(campaign_id, _, _)| *campaign_id)
+			.unwrap();
+		System::set_block_number(3 + ClaimDuration::get());
+		assert_ok!(Task::claim_reward(Origin::signed(USER1), campaign_id, 700));
+		assert_eq!(Balances::free_balance(USER1), 700);
+	});
+}
+
+#[test]
+fn migration_to_v3_should_move_remaining_balances_in_later_blocks() {
+	use crate::migrations::v3::{balance_key, BALANCES_PER_BLOCK, LEGACY_CAMPAIGN};
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<Task>();
+		let users = BALANCES_PER_BLOCK + 1;
+		for index in 0..users {
+			let mut user = [0xffu8; 32];
+			user[..4].copy_from_slice(&index.to_le_bytes());
+			put_storage_value(
+				b"Task",
+				b"BalanceUser",
+				&balance_key::<Test>(&AccountId::new(user), &RewardAsset::Native),
+				(1u64, 10u128),
+			);
+		}
+
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert!(LegacyBalancesLeft::<Test>::get());
+		assert_eq!(Rewards::<Test>::iter().count() as u32, BALANCES_PER_BLOCK);
+
+		Task::on_idle(2, u64::MAX);
+		assert!(!LegacyBalancesLeft::<Test>::get());
+		assert_eq!(Rewards::<Test>::iter().count() as u32, users);
+		assert!(Rewards::<Test>::iter()
+			.all(|(_, campaign_id, reward)| campaign_id == LEGACY_CAMPAIGN &&
+				reward.unclaimed() == 10));
+	});
+}
+
+#[test]
+fn retried_payment_should_skip_rewarded_users() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::add_task(Origin::signed(BOB), campaign_id, TaskKind::Quiz, 100, 10, 50));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, Some(0), vec![USER1], 100));
+		assert!(Task::rewarded((campaign_id, 0), USER1));
+
+		// users rewarded before, or repeated in the batch, are only rewarded once
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			1,
+			Some(0),
+			vec![USER1, USER2, USER2],
+			100
+		));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DuplicatesSkipped {
+				campaign_id,
+				task_id: Some(0),
+				account: vec![USER1, USER2].try_into().unwrap(),
+			}
+			.into()));
+		System::assert_last_event(
+			Event::Payment {
+				campaign_id,
+				task_id: Some(0),
+				account: vec![USER2].try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 100);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 100);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().spent, 200);
+
+		// a retry of the whole batch rewards nobody
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			2,
+			Some(0),
+			vec![USER1, USER2],
+			100
+		));
+		System::assert_last_event(
+			Event::DuplicatesSkipped {
+				campaign_id,
+				task_id: Some(0),
+				account: vec![USER1, USER2].try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().spent, 200);
+	});
+}
+
+#[test]
+fn retried_payments_should_reward_nobody() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 0, None, vec![USER1], 100));
+
+		// a campaign without tasks rewards a user in each of its batches, only repetitions
+		// within a payment are skipped
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			1,
+			None,
+			vec![USER1, USER2, USER2],
+			100
+		));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DuplicatesSkipped {
+				campaign_id,
+				task_id: None,
+				account: vec![USER2].try_into().unwrap(),
+			}
+			.into()));
+		System::assert_last_event(
+			Event::Payment {
+				campaign_id,
+				task_id: None,
+				account: vec![USER1, USER2].try_into().unwrap(),
+			}
+			.into(),
+		);
+		assert_ok!(Task::payment(Origin::signed(ADMIN), campaign_id, 2, None, vec![USER1], 100));
+
+		// a retried batch credits nothing
+		assert_ok!(Task::payment(
+			Origin::signed(ADMIN),
+			campaign_id,
+			1,
+			None,
+			vec![USER1, USER2],
+			100
+		));
+		System::assert_last_event(Event::BatchAlreadyPaid { campaign_id, batch_id: 1 }.into());
+		assert_eq!(Task::claimable(&USER1, RewardAsset::Native).0, 300);
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 100);
+		assert_eq!(Task::campaigns(campaign_id).unwrap().spent, 400);
+
+		// batch ids are only unique within a campaign
+		assert_ok!(Task::create_campaign(
+			Origin::signed(ALICE),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_ok!(Task::payment(Origin::signed(ADMIN), 1, 1, None, vec![USER2], 100));
+		assert_eq!(Task::claimable(&USER2, RewardAsset::Native).0, 200);
+	});
+}
+
+#[test]
+fn platform_fee_should_be_paid_on_funding() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		PlatformFee::set(Permill::from_percent(5));
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DepositClient {
+				campaign_id,
+				asset: RewardAsset::Native,
+				deposit_amount: 5000,
+				fee: 250,
+			}
+			.into()));
+		// the whole value is escrowed, the fee goes to `FeeDestination`, burnt in the mock
+		assert_eq!(Balances::free_balance(Task::account_id()), 5000);
+		assert_eq!(Balances::free_balance(BOB), 100000 - 5000 - 250 - 1000);
+		assert_eq!(Balances::total_issuance(), issuance - 250);
+
+		// the fee is not refunded with the unused budget
+		assert_ok!(Task::cancel_campaign(Origin::signed(BOB), campaign_id));
+		assert_eq!(Balances::free_balance(BOB), 100000 - 250);
+
+		// a campaign in an asset pays the fee in the asset, to the treasury
+		assert_ok!(Assets::force_create(Origin::root(), 7, ALICE, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 7, BOB, 10000));
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"1".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Asset(7),
+			None
+		));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DepositClient {
+				campaign_id: campaign_id + 1,
+				asset: RewardAsset::Asset(7),
+				deposit_amount: 5000,
+				fee: 250,
+			}
+			.into()));
+		assert_eq!(Assets::balance(7, Task::account_id()), 5000 + 1);
+		assert_eq!(Assets::balance(7, TREASURY), 250);
+		assert_eq!(Assets::balance(7, BOB), 10000 - 5000 - 1 - 250);
+	});
+}
+
+#[test]
+fn asset_fees_should_wait_until_the_treasury_can_receive_them() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		PlatformFee::set(Permill::from_percent(5));
+		assert_ok!(Assets::force_create(Origin::root(), 9, ALICE, true, 100));
+		assert_ok!(Assets::mint(Origin::signed(ALICE), 9, BOB, 10000));
+
+		// 50 is below the minimum balance, the treasury has no account to receive it
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"0".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Asset(9),
+			None
+		));
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DepositClient {
+				campaign_id: 0,
+				asset: RewardAsset::Asset(9),
+				deposit_amount: 1000,
+				fee: 50,
+			}
+			.into()));
+		assert_eq!(PendingFees::<Test>::get(9), 50);
+		assert_eq!(Assets::balance(9, TREASURY), 0);
+		assert_eq!(Assets::balance(9, Task::account_id()), 1000 + 100 + 50);
+
+		// paid once they reach it
+		assert_ok!(Task::create_campaign(
+			Origin::signed(BOB),
+			"1".as_bytes().to_vec(),
+			1000,
+			0,
+			100,
+			RewardAsset::Asset(9),
+			None
+		));
+		assert_eq!(PendingFees::<Test>::get(9), 0);
+		assert_eq!(Assets::balance(9, TREASURY), 100);
+		assert_eq!(Assets::balance(9, Task::account_id()), 2000 + 100);
+		assert_eq!(Assets::balance(9, BOB), 10000 - 2000 - 100 - 100);
+	});
+}
+
+#[test]
+fn treasury_should_fund_campaigns() {
+	new_test_ext().execute_with(|| {
+		let campaign_id: CampaignId = 0;
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&TREASURY, 100000);
+		PlatformFee::set(Permill::from_percent(5));
+
+		assert_noop!(
+			Task::create_treasury_campaign(
+				Origin::signed(ALICE),
+				"0".as_bytes().to_vec(),
+				5000,
+				0,
+				100,
+				RewardAsset::Native,
+				None
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Task::create_treasury_campaign(
+			Origin::root(),
+			"0".as_bytes().to_vec(),
+			5000,
+			0,
+			100,
+			RewardAsset::Native,
+			None
+		));
+		assert_eq!(Task::campaigns(campaign_id).unwrap().client, TREASURY);
+		// the treasury pays no fee to itself
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::DepositClient {
+				campaign_id,
+				asset: RewardAsset::Native,
+				deposit_amount: 5000,
+				fee: 0,
+			}
+			.into()));
+		assert_eq!(Balances::free_balance(Task::account_id()), 5000);
+		assert_eq!(Balances::free_balance(TREASURY), 100000 - 5000 - 1000);
+
+		// managed through `RewardOrigin`, the unused budget goes back to the pot
+		assert_ok!(Task::cancel_campaign(Origin::root(), campaign_id));
+		assert_eq!(Balances::free_balance(TREASURY), 100000);
+	});
+}
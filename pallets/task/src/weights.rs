@@ -0,0 +1,369 @@
+//! Weights for pallet_task
+//!
+//! NOT GENERATED YET: the values below are placeholders, upper bounds counted by hand from the
+//! storage accesses of the worst case of each call, no benchmark has been run for them.
+//! Replace this whole file with the output of `scripts/benchmark.sh`, which runs the benchmarks
+//! of `benchmarking.rs` on the reference hardware with `.maintain/frame-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_task.
+pub trait WeightInfo {
+	fn create_campaign() -> Weight;
+	fn create_treasury_campaign() -> Weight;
+	fn start_campaign() -> Weight;
+	fn pause_campaign() -> Weight;
+	fn resume_campaign() -> Weight;
+	fn end_campaign() -> Weight;
+	fn settle_campaign() -> Weight;
+	fn cancel_campaign() -> Weight;
+	fn slash_bond() -> Weight;
+	fn add_task() -> Weight;
+	fn payment(u: u32) -> Weight;
+	fn payment_vested(u: u32) -> Weight;
+	fn submit_proof() -> Weight;
+	fn approve_submission() -> Weight;
+	fn reject_submission() -> Weight;
+	fn claim() -> Weight;
+	fn claim_reward() -> Weight;
+	fn claim_all(c: u32) -> Weight;
+	fn publish_distribution() -> Weight;
+	fn claim_distribution(p: u32) -> Weight;
+	fn set_vesting() -> Weight;
+	fn claim_vested(v: u32) -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
+	fn add_admin() -> Weight;
+	fn remove_admin() -> Weight;
+	fn propose_admin_handover() -> Weight;
+	fn accept_admin_handover() -> Weight;
+	fn force_set_admins(a: u32) -> Weight;
+}
+
+/// Weights for pallet_task using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_campaign() -> Weight {
+		(78_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn create_treasury_campaign() -> Weight {
+		(77_900_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn start_campaign() -> Weight {
+		(24_100_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn pause_campaign() -> Weight {
+		(23_600_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn resume_campaign() -> Weight {
+		(23_700_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn end_campaign() -> Weight {
+		(23_500_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn settle_campaign() -> Weight {
+		(61_200_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_campaign() -> Weight {
+		(60_800_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn slash_bond() -> Weight {
+		(41_700_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn add_task() -> Weight {
+		(27_200_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn payment(u: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((23_000_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(u as Weight)))
+	}
+	fn payment_vested(u: u32) -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add((26_000_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(u as Weight)))
+	}
+	fn submit_proof() -> Weight {
+		(29_800_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn approve_submission() -> Weight {
+		(78_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn reject_submission() -> Weight {
+		(27_500_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn claim() -> Weight {
+		(52_700_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn claim_reward() -> Weight {
+		(50_900_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn claim_all(c: u32) -> Weight {
+		(12_400_000 as Weight)
+			.saturating_add((44_800_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	fn publish_distribution() -> Weight {
+		(38_200_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn claim_distribution(p: u32) -> Weight {
+		(63_400_000 as Weight)
+			.saturating_add((1_100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn set_vesting() -> Weight {
+		(23_800_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_vested(v: u32) -> Weight {
+		(45_300_000 as Weight)
+			.saturating_add((900_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn grant_role() -> Weight {
+		(24_600_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_role() -> Weight {
+		(26_100_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_admin() -> Weight {
+		(22_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_admin() -> Weight {
+		(24_700_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn propose_admin_handover() -> Weight {
+		(21_900_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_admin_handover() -> Weight {
+		(28_300_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn force_set_admins(a: u32) -> Weight {
+		(18_500_000 as Weight)
+			.saturating_add((2_900_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((a as Weight).saturating_mul(2 as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_campaign() -> Weight {
+		(78_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn create_treasury_campaign() -> Weight {
+		(77_900_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn start_campaign() -> Weight {
+		(24_100_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn pause_campaign() -> Weight {
+		(23_600_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resume_campaign() -> Weight {
+		(23_700_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn end_campaign() -> Weight {
+		(23_500_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn settle_campaign() -> Weight {
+		(61_200_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_campaign() -> Weight {
+		(60_800_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn slash_bond() -> Weight {
+		(41_700_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_task() -> Weight {
+		(27_200_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn payment(u: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((23_000_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(u as Weight)))
+	}
+	fn payment_vested(u: u32) -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add((26_000_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(u as Weight)))
+	}
+	fn submit_proof() -> Weight {
+		(29_800_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn approve_submission() -> Weight {
+		(78_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn reject_submission() -> Weight {
+		(27_500_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn claim() -> Weight {
+		(52_700_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn claim_reward() -> Weight {
+		(50_900_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn claim_all(c: u32) -> Weight {
+		(12_400_000 as Weight)
+			.saturating_add((44_800_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	fn publish_distribution() -> Weight {
+		(38_200_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn claim_distribution(p: u32) -> Weight {
+		(63_400_000 as Weight)
+			.saturating_add((1_100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_vesting() -> Weight {
+		(23_800_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn claim_vested(v: u32) -> Weight {
+		(45_300_000 as Weight)
+			.saturating_add((900_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn grant_role() -> Weight {
+		(24_600_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_role() -> Weight {
+		(26_100_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_admin() -> Weight {
+		(22_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_admin() -> Weight {
+		(24_700_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn propose_admin_handover() -> Weight {
+		(21_900_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_admin_handover() -> Weight {
+		(28_300_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn force_set_admins(a: u32) -> Weight {
+		(18_500_000 as Weight)
+			.saturating_add((2_900_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((a as Weight).saturating_mul(2 as Weight)))
+	}
+}
@@ -41,7 +41,11 @@ pub use sp_runtime::{Perbill, Permill};
 
 use beefy_primitives::{crypto::AuthorityId as BeefyId, mmr::MmrLeafVersion};
 use codec::Encode;
-use frame_support::{weights::DispatchClass, PalletId};
+use frame_support::{
+	traits::{Contains, Currency, EnsureOneOf, Imbalance, OnUnbalanced},
+	weights::DispatchClass,
+	PalletId,
+};
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
 	EnsureRoot,
@@ -116,10 +120,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("appchain-plats"),
 	impl_name: create_runtime_str!("plats"),
 	authoring_version: 1,
-	spec_version: 105,
+	spec_version: 107,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -226,9 +230,22 @@ const_assert!(NORMAL_DISPATCH_RATIO.deconstruct() >= AVERAGE_ON_INITIALIZE_RATIO
 
 // Configure FRAME pallets to include in runtime.
 
+/// Keep the badge classes of campaigns out of reach of `pallet_uniques::create`, so their ids can
+/// not be taken before the campaigns are created
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		!matches!(
+			call,
+			Call::OctopusUniques(pallet_uniques::Call::create { class, .. })
+				if *class >= BadgeClassStart::get()
+		)
+	}
+}
+
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = RuntimeBlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -361,12 +378,40 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type Event = Event;
-	type DustRemoval = ();
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
 
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Credit the block author
+pub struct Author;
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		if let Some(author) = Authorship::author() {
+			Balances::resolve_creating(&author, amount);
+		}
+	}
+}
+
+/// Send 80% of transaction fees to the treasury and 20% to the block author, tips go to the
+/// author in full
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (to_treasury, mut to_author) = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.merge_into(&mut to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			Author::on_unbalanced(to_author);
+		}
+	}
+}
+
 parameter_types! {
 	pub const TransactionByteFee: Balance = 10 * currency::MILLICENTS;
 	pub const OperationalFeeMultiplier: u8 = 5;
@@ -376,7 +421,7 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = IdentityFee<Balance>;
@@ -384,6 +429,35 @@ impl pallet_transaction_payment::Config for Runtime {
 		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = currency::PLAT;
+	pub const ProposalBondMaximum: Option<Balance> = Some(100 * currency::PLAT);
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::zero();
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+	pub TreasuryAccount: AccountId = Treasury::account_id();
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ProposalBondMaximum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
 parameter_types! {
 	pub const UncleGenerations: BlockNumber = 0;
 }
@@ -506,8 +580,8 @@ parameter_types! {
 	pub const ValueLimit: u32 = 256;
 }
 
-type ClassId = u128;
-type InstanceId = u128;
+pub type ClassId = u128;
+pub type InstanceId = u128;
 
 impl pallet_uniques::Config<pallet_uniques::Instance1> for Runtime {
 	type Event = Event;
@@ -655,23 +729,69 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+pub struct TaskAppCrypto;
+
+impl frame_system::offchain::AppCrypto<<Signature as Verify>::Signer, Signature> for TaskAppCrypto {
+	type RuntimeAppPublic = pallet_task::AuthorityId;
+	type GenericSignature = sp_core::sr25519::Signature;
+	type GenericPublic = sp_core::sr25519::Public;
+}
+
 parameter_types! {
 	pub const CampaignDepositMinimum: Balance = currency::PLAT;
 	pub const TaskPalletId: PalletId = PalletId(*b"plt/task");
 	pub const ClaimDuration: BlockNumber = 2;
 	pub const MaxTasks: u32 = 10;
+	pub const MaxVerificationsPerBlock: u32 = 10;
+	pub const MaxVestingPositions: u32 = 32;
 	pub const PayoutDuration : BlockNumber = 6;
 	pub const CampaignDeposit: Permill = Permill::from_percent(2);
+	pub const PlatformFee: Permill = Permill::from_percent(1);
+	pub const MaxCampaignRefLength: u32 = 64;
+	pub const MaxPendingPayouts: u32 = 10_000;
+	pub const MaxPayoutsPerBlock: u32 = 100;
+	pub const MaxUsersPerPayment: u32 = 500;
+	pub const MaxProofLength: u32 = 32;
+	pub const MinAdmins: u32 = 1;
+	pub const MaxAdmins: u32 = 16;
+	// Keep campaign badges clear of the classes bridged by the appchain pallet
+	pub const BadgeClassStart: ClassId = 1 << 64;
 }
 impl pallet_task::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type CampaignDepositMinimum = CampaignDepositMinimum;
 	type CampaignDeposit = CampaignDeposit;
-	// type RewardOrigin = EnsureRoot<AccountId>;
+	type Slash = Treasury;
+	type PlatformFee = PlatformFee;
+	type FeeDestination = Treasury;
+	type TreasuryOrigin = EnsureRoot<AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type RewardOrigin = EnsureOneOf<EnsureRoot<AccountId>, pallet_task::EnsureAdmin<Runtime>>;
+	type AdminOrigin = EnsureRoot<AccountId>;
 	type ClaimDuration = ClaimDuration;
 	type PayoutDuration = PayoutDuration;
 	type PalletId = TaskPalletId;
+	type MaxCampaignRefLength = MaxCampaignRefLength;
+	type MaxPendingPayouts = MaxPendingPayouts;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type MaxUsersPerPayment = MaxUsersPerPayment;
+	type MaxProofLength = MaxProofLength;
+	type MaxTasks = MaxTasks;
+	type MaxVestingPositions = MaxVestingPositions;
+	type AuthorityId = TaskAppCrypto;
+	type MaxVerificationsPerBlock = MaxVerificationsPerBlock;
+	type MinAdmins = MinAdmins;
+	type MaxAdmins = MaxAdmins;
+	type AssetId = AssetId;
+	type Assets = OctopusAssets;
+	type ClassId = ClassId;
+	type InstanceId = InstanceId;
+	type Uniques = OctopusUniques;
+	type BadgeClassStart = BadgeClassStart;
+	type BadgeClassDeposit = ClassDeposit;
+	type BadgeDeposit = InstanceDeposit;
+	type WeightInfo = pallet_task::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -702,6 +822,7 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Pallet task
 		Task: pallet_task,
+		Treasury: pallet_treasury,
 	}
 );
 
@@ -745,6 +866,8 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
+		[pallet_treasury, Treasury]
+		[pallet_task, Task]
 	);
 }
 
@@ -928,6 +1051,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_task_rpc_runtime_api::TaskRuntimeApi<Block, AccountId, Balance, BlockNumber, AssetId, ClassId>
+		for Runtime
+	{
+		fn campaign(campaign_id: pallet_task::CampaignId) -> Option<pallet_task::CampaignOf<Runtime>> {
+			Task::campaign_info(campaign_id)
+		}
+		fn campaign_id(campaign_ref: Vec<u8>) -> Option<pallet_task::CampaignId> {
+			Task::campaign_id_of(campaign_ref)
+		}
+		fn remaining_budget(campaign_id: pallet_task::CampaignId) -> Option<Balance> {
+			Task::remaining_budget(campaign_id)
+		}
+		fn claimable(
+			who: AccountId,
+			asset: pallet_task::RewardAsset<AssetId>,
+		) -> (Balance, BlockNumber) {
+			Task::claimable(&who, asset)
+		}
+		fn vesting_balance(
+			who: AccountId,
+			asset: pallet_task::RewardAsset<AssetId>,
+		) -> (Balance, Balance) {
+			Task::vesting_balance(&who, asset)
+		}
+		fn remain_balance() -> Balance {
+			Task::remain_balance()
+		}
+	}
+
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
 		fn validator_set() -> Option<beefy_primitives::ValidatorSet<BeefyId>> {
 			Beefy::validator_set()
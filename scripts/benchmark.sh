@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+# Regenerates the weights of pallet_task on the reference hardware
+set -e
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+echo "*** Building the node with runtime benchmarks ***"
+cargo build --release -p plats --features runtime-benchmarks
+
+echo "*** Benchmarking pallet_task ***"
+./target/release/plats benchmark \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_task \
+	--extrinsic='*' \
+	--execution=wasm \
+	--wasm-execution=compiled \
+	--heap-pages=4096 \
+	--template=./.maintain/frame-weight-template.hbs \
+	--output=./pallets/task/src/weights.rs